@@ -0,0 +1,268 @@
+use super::{Handler, Request, Response};
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+  "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// Howard Hinnant's "days_from_civil" algorithm, converting a civil
+// (year, month, day) triple into a day count relative to the Unix epoch
+// using only integer arithmetic.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let y = if month <= 2 { year - 1 } else { year };
+  let era = (if y >= 0 { y } else { y - 399 }) / 400;
+  let yoe = y - era * 400;
+  let mp = (month + 9) % 12;
+  let doy = (153 * mp + 2) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era * 146097 + doe - 719468
+}
+
+/// Parses an RFC 7231 IMF-fixdate string (e.g. `Sun, 06 Nov 1994 08:49:37
+/// GMT`), the form used by `Last-Modified` and `If-Modified-Since`, into
+/// seconds since the Unix epoch. Returns `None` for anything else, including
+/// the obsolete RFC 850 and asctime date forms.
+fn parse_http_date(input: &str) -> Option<i64> {
+  let (_weekday, rest) = input.trim().split_once(", ")?;
+
+  let mut parts = rest.split_whitespace();
+  let day: i64 = parts.next()?.parse().ok()?;
+  let month = parts.next()?;
+  let year: i64 = parts.next()?.parse().ok()?;
+  let time = parts.next()?;
+  if parts.next() != Some("GMT") {
+    return None;
+  }
+
+  let month = MONTHS.iter().position(|&m| m == month)? as i64 + 1;
+
+  let mut time_parts = time.split(':');
+  let hour: i64 = time_parts.next()?.parse().ok()?;
+  let minute: i64 = time_parts.next()?.parse().ok()?;
+  let second: i64 = time_parts.next()?.parse().ok()?;
+
+  let days = days_from_civil(year, month, day);
+  Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Returns `true` if any of the comma-separated entity tags in an
+/// `If-None-Match` header value matches `etag`, ignoring the `W/` weak
+/// validator prefix, or if the header is the wildcard `*`.
+fn if_none_match_matches(if_none_match: &str, etag: &str) -> bool {
+  let etag = etag.trim().trim_start_matches("W/");
+
+  if_none_match.split(',').any(|candidate| {
+    let candidate = candidate.trim();
+    candidate == "*" || candidate.trim_start_matches("W/") == etag
+  })
+}
+
+/// Wraps a [`Handler`] with ETag/`Last-Modified` conditional-request support.
+///
+/// After the inner handler produces a [`Response`], `ConditionalHandler`
+/// compares the request's `If-None-Match`/`If-Modified-Since` headers
+/// against the response's `ETag`/`Last-Modified` headers. If they indicate
+/// the client's cached copy is still current, the response is rewritten to
+/// `304 Not Modified` with an empty body, preserving all other headers
+/// (including the validators) unchanged.
+///
+/// Per [RFC 7232](https://www.rfc-editor.org/rfc/rfc7232#section-6),
+/// `If-None-Match` takes precedence over `If-Modified-Since` - the date is
+/// only consulted when the response carries no `ETag`.
+///
+/// # Example
+///
+/// ```
+/// use lang_handler::{ConditionalHandler, Handler, Request, Response};
+///
+/// struct StaticHandler;
+///
+/// impl Handler for StaticHandler {
+///   type Error = String;
+///
+///   fn handle(&self, _request: Request) -> Result<Response, Self::Error> {
+///     let response = Response::builder()
+///       .status(200)
+///       .header("ETag", "\"abc123\"")
+///       .body("Hello, World!")
+///       .build();
+///
+///     Ok(response)
+///   }
+/// }
+///
+/// let handler = ConditionalHandler::new(StaticHandler);
+///
+/// let request = Request::builder()
+///   .url("http://example.com/index.html")
+///   .header("If-None-Match", "\"abc123\"")
+///   .build()
+///   .expect("should build request");
+///
+/// let response = handler.handle(request).expect("should handle request");
+/// assert_eq!(response.status(), 304);
+/// assert_eq!(response.body(), "");
+/// assert_eq!(response.headers().get("ETag"), Some("\"abc123\"".to_string()));
+/// ```
+pub struct ConditionalHandler<H: Handler>(H);
+
+impl<H: Handler> ConditionalHandler<H> {
+  /// Wraps `handler` with conditional-request support.
+  pub fn new(handler: H) -> Self {
+    Self(handler)
+  }
+
+  /// Returns `true` if `request`'s conditional headers indicate the client's
+  /// cached copy of `response` is still current.
+  fn is_not_modified(request: &Request, response: &Response) -> bool {
+    if let Some(if_none_match) = request.headers().get("If-None-Match") {
+      return match response.headers().get("ETag") {
+        Some(etag) => if_none_match_matches(&if_none_match, &etag),
+        None => false,
+      };
+    }
+
+    if let Some(if_modified_since) = request.headers().get("If-Modified-Since") {
+      return match response.headers().get("Last-Modified") {
+        Some(last_modified) => {
+          match (parse_http_date(&if_modified_since), parse_http_date(&last_modified)) {
+            (Some(since), Some(modified)) => modified <= since,
+            _ => false,
+          }
+        }
+        None => false,
+      };
+    }
+
+    false
+  }
+}
+
+impl<H: Handler> Handler for ConditionalHandler<H> {
+  type Error = H::Error;
+
+  fn handle(&self, request: Request) -> Result<Response, Self::Error> {
+    let response = self.0.handle(request.clone())?;
+
+    if Self::is_not_modified(&request, &response) {
+      return Ok(response.extend().status(304).body("").build());
+    }
+
+    Ok(response)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::ResponseBuilder;
+
+  struct FixedHandler(Response);
+
+  impl Handler for FixedHandler {
+    type Error = String;
+
+    fn handle(&self, _request: Request) -> Result<Response, Self::Error> {
+      Ok(self.0.clone())
+    }
+  }
+
+  #[test]
+  fn passes_through_when_no_conditional_headers_are_sent() {
+    let response = ResponseBuilder::new().status(200).header("ETag", "\"abc\"").body("hi").build();
+
+    let handler = ConditionalHandler::new(FixedHandler(response));
+
+    let request = Request::builder()
+      .url("http://example.com")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body(), "hi");
+  }
+
+  #[test]
+  fn returns_304_when_if_none_match_matches_etag() {
+    let response = ResponseBuilder::new().status(200).header("ETag", "\"abc\"").body("hi").build();
+
+    let handler = ConditionalHandler::new(FixedHandler(response));
+
+    let request = Request::builder()
+      .url("http://example.com")
+      .header("If-None-Match", "\"abc\"")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 304);
+    assert_eq!(response.body(), "");
+    assert_eq!(response.headers().get("ETag"), Some("\"abc\"".to_string()));
+  }
+
+  #[test]
+  fn if_none_match_takes_precedence_over_if_modified_since() {
+    let response = ResponseBuilder::new()
+      .status(200)
+      .header("ETag", "\"abc\"")
+      .header("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT")
+      .body("hi")
+      .build();
+
+    let handler = ConditionalHandler::new(FixedHandler(response));
+
+    // A stale If-None-Match must not be rescued by a satisfied
+    // If-Modified-Since.
+    let request = Request::builder()
+      .url("http://example.com")
+      .header("If-None-Match", "\"different\"")
+      .header("If-Modified-Since", "Mon, 07 Nov 1994 08:49:37 GMT")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 200);
+  }
+
+  #[test]
+  fn returns_304_when_not_modified_since() {
+    let response = ResponseBuilder::new()
+      .status(200)
+      .header("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT")
+      .body("hi")
+      .build();
+
+    let handler = ConditionalHandler::new(FixedHandler(response));
+
+    let request = Request::builder()
+      .url("http://example.com")
+      .header("If-Modified-Since", "Mon, 07 Nov 1994 08:49:37 GMT")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 304);
+    assert_eq!(response.body(), "");
+  }
+
+  #[test]
+  fn passes_through_when_modified_after_if_modified_since() {
+    let response = ResponseBuilder::new()
+      .status(200)
+      .header("Last-Modified", "Mon, 07 Nov 1994 08:49:37 GMT")
+      .body("hi")
+      .build();
+
+    let handler = ConditionalHandler::new(FixedHandler(response));
+
+    let request = Request::builder()
+      .url("http://example.com")
+      .header("If-Modified-Since", "Sun, 06 Nov 1994 08:49:37 GMT")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 200);
+  }
+}
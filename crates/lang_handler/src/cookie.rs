@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::percent::{percent_decode, percent_encode, EncodeSet};
+
+/// The `SameSite` attribute of a cookie, controlling whether it is sent with
+/// cross-site requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+  /// The cookie is only sent with same-site requests.
+  Strict,
+
+  /// The cookie is sent with same-site requests and top-level navigations.
+  Lax,
+
+  /// The cookie is sent with all requests, including cross-site ones.
+  /// Requires `Secure` to be accepted by browsers.
+  None,
+}
+
+impl fmt::Display for SameSite {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SameSite::Strict => write!(f, "Strict"),
+      SameSite::Lax => write!(f, "Lax"),
+      SameSite::None => write!(f, "None"),
+    }
+  }
+}
+
+/// Options controlling how a cookie set via [`Cookie`] is emitted in a
+/// `Set-Cookie` header.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::{Cookie, SameSite};
+/// let cookie = Cookie::new("session", "abc123")
+///   .path("/")
+///   .secure(true)
+///   .http_only(true)
+///   .same_site(SameSite::Lax);
+///
+/// assert_eq!(
+///   cookie.to_string(),
+///   "session=abc123; Path=/; Secure; HttpOnly; SameSite=Lax"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cookie {
+  name: String,
+  value: String,
+  path: Option<String>,
+  domain: Option<String>,
+  max_age: Option<i64>,
+  expires: Option<String>,
+  secure: bool,
+  http_only: bool,
+  same_site: Option<SameSite>,
+}
+
+impl Cookie {
+  /// Creates a new cookie with just a name and value. All other attributes
+  /// default to unset.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Cookie;
+  /// let cookie = Cookie::new("session", "abc123");
+  ///
+  /// assert_eq!(cookie.to_string(), "session=abc123");
+  /// ```
+  pub fn new<N, V>(name: N, value: V) -> Self
+  where
+    N: Into<String>,
+    V: Into<String>,
+  {
+    Self {
+      name: name.into(),
+      value: value.into(),
+      path: None,
+      domain: None,
+      max_age: None,
+      expires: None,
+      secure: false,
+      http_only: false,
+      same_site: None,
+    }
+  }
+
+  /// Sets the `Path` attribute.
+  pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+    self.path = Some(path.into());
+    self
+  }
+
+  /// Sets the `Domain` attribute.
+  pub fn domain<D: Into<String>>(mut self, domain: D) -> Self {
+    self.domain = Some(domain.into());
+    self
+  }
+
+  /// Sets the `Max-Age` attribute, in seconds.
+  pub fn max_age(mut self, max_age: i64) -> Self {
+    self.max_age = Some(max_age);
+    self
+  }
+
+  /// Sets the `Expires` attribute to a pre-formatted HTTP-date string.
+  pub fn expires<E: Into<String>>(mut self, expires: E) -> Self {
+    self.expires = Some(expires.into());
+    self
+  }
+
+  /// Sets the `Secure` attribute.
+  pub fn secure(mut self, secure: bool) -> Self {
+    self.secure = secure;
+    self
+  }
+
+  /// Sets the `HttpOnly` attribute.
+  pub fn http_only(mut self, http_only: bool) -> Self {
+    self.http_only = http_only;
+    self
+  }
+
+  /// Sets the `SameSite` attribute.
+  pub fn same_site(mut self, same_site: SameSite) -> Self {
+    self.same_site = Some(same_site);
+    self
+  }
+
+  /// Returns the name of the cookie.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the value of the cookie.
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+}
+
+impl fmt::Display for Cookie {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}={}", self.name, percent_encode(&self.value, EncodeSet::Component))?;
+
+    if let Some(path) = &self.path {
+      write!(f, "; Path={}", path)?;
+    }
+
+    if let Some(domain) = &self.domain {
+      write!(f, "; Domain={}", domain)?;
+    }
+
+    if let Some(max_age) = &self.max_age {
+      write!(f, "; Max-Age={}", max_age)?;
+    }
+
+    if let Some(expires) = &self.expires {
+      write!(f, "; Expires={}", expires)?;
+    }
+
+    if self.secure {
+      write!(f, "; Secure")?;
+    }
+
+    if self.http_only {
+      write!(f, "; HttpOnly")?;
+    }
+
+    if let Some(same_site) = &self.same_site {
+      write!(f, "; SameSite={}", same_site)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Parses the value of an inbound `Cookie` header into an ordered list of
+/// `(name, value)` pairs.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::parse_cookie_header;
+/// let cookies = parse_cookie_header("session=abc123; theme=dark");
+///
+/// assert_eq!(cookies, vec![
+///   ("session".to_string(), "abc123".to_string()),
+///   ("theme".to_string(), "dark".to_string()),
+/// ]);
+/// ```
+pub fn parse_cookie_header(header: &str) -> Vec<(String, String)> {
+  header
+    .split(';')
+    .filter_map(|pair| {
+      let pair = pair.trim();
+      if pair.is_empty() {
+        return None;
+      }
+
+      let (name, value) = pair.split_once('=')?;
+      Some((name.trim().to_string(), percent_decode(value.trim())))
+    })
+    .collect()
+}
+
+/// Parses the name/value pair out of a single `Set-Cookie` header value,
+/// discarding its attributes (`Path`, `Domain`, `Max-Age`, etc.). Returns
+/// `None` if the header doesn't contain a `name=value` pair.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::parse_set_cookie_header;
+/// let cookie = parse_set_cookie_header("session=abc%20123; Path=/; Secure");
+///
+/// assert_eq!(cookie, Some(("session".to_string(), "abc 123".to_string())));
+/// ```
+pub fn parse_set_cookie_header(header: &str) -> Option<(String, String)> {
+  let pair = header.split(';').next()?.trim();
+  let (name, value) = pair.split_once('=')?;
+  Some((name.trim().to_string(), percent_decode(value.trim())))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn formats_cookie_with_all_attributes() {
+    let cookie = Cookie::new("session", "abc 123")
+      .path("/")
+      .domain("example.com")
+      .max_age(3600)
+      .secure(true)
+      .http_only(true)
+      .same_site(SameSite::Strict);
+
+    assert_eq!(
+      cookie.to_string(),
+      "session=abc%20123; Path=/; Domain=example.com; Max-Age=3600; Secure; HttpOnly; SameSite=Strict"
+    );
+  }
+
+  #[test]
+  fn parses_cookie_header_into_pairs() {
+    let cookies = parse_cookie_header("session=abc%20123; theme=dark");
+
+    assert_eq!(cookies, vec![
+      ("session".to_string(), "abc 123".to_string()),
+      ("theme".to_string(), "dark".to_string()),
+    ]);
+  }
+
+  #[test]
+  fn parses_set_cookie_header_into_a_pair() {
+    let cookie = parse_set_cookie_header("session=abc%20123; Path=/; Secure; HttpOnly");
+
+    assert_eq!(cookie, Some(("session".to_string(), "abc 123".to_string())));
+  }
+
+  #[test]
+  fn rejects_set_cookie_header_without_a_pair() {
+    assert_eq!(parse_set_cookie_header("not-a-pair"), None);
+  }
+}
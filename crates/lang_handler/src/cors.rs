@@ -0,0 +1,296 @@
+use std::path::PathBuf;
+
+use super::{rewrite::Condition, Handler, Request, Response};
+
+/// Configures which origins, methods, and headers a [`CorsHandler`] allows.
+///
+/// # Example
+///
+/// ```
+/// use lang_handler::CorsPolicy;
+///
+/// let policy = CorsPolicy::new()
+///   .allow_origin("https://example.com")
+///   .allow_methods(["GET", "POST"])
+///   .allow_headers(["Content-Type"])
+///   .allow_credentials(true);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CorsPolicy {
+  allow_any_origin: bool,
+  allowed_origins: Vec<String>,
+  allowed_methods: Vec<String>,
+  allowed_headers: Vec<String>,
+  allow_credentials: bool,
+}
+
+impl CorsPolicy {
+  /// Creates a new policy allowing nothing until configured.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Allows the given origin, e.g. `"https://example.com"`.
+  pub fn allow_origin<O: Into<String>>(mut self, origin: O) -> Self {
+    self.allowed_origins.push(origin.into());
+    self
+  }
+
+  /// Allows every origin. The actual request `Origin` is still echoed back
+  /// rather than `*`, so this composes with `allow_credentials`.
+  pub fn allow_any_origin(mut self, allow: bool) -> Self {
+    self.allow_any_origin = allow;
+    self
+  }
+
+  /// Sets the methods advertised in `Access-Control-Allow-Methods`.
+  pub fn allow_methods<I, S>(mut self, methods: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    self.allowed_methods = methods.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Sets the headers advertised in `Access-Control-Allow-Headers`.
+  pub fn allow_headers<I, S>(mut self, headers: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    self.allowed_headers = headers.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Sets whether `Access-Control-Allow-Credentials: true` is sent.
+  pub fn allow_credentials(mut self, allow: bool) -> Self {
+    self.allow_credentials = allow;
+    self
+  }
+
+  /// Returns the request's `Origin` header if this policy allows it.
+  fn matching_origin(&self, request: &Request) -> Option<String> {
+    let origin = request.headers().get("Origin")?;
+
+    if self.allow_any_origin || self.allowed_origins.iter().any(|allowed| allowed == &origin) {
+      Some(origin)
+    } else {
+      None
+    }
+  }
+}
+
+/// Wraps a [`Handler`] with CORS support driven by a [`CorsPolicy`].
+///
+/// When an incoming request's `Origin` header matches the policy,
+/// `Access-Control-Allow-Origin` (echoing back that single origin, never a
+/// comma-joined list) and, if configured, `Access-Control-Allow-Credentials`
+/// are added to the response. `OPTIONS` preflight requests - those carrying
+/// `Access-Control-Request-Method` - are short-circuited with a `204`
+/// response carrying the full set of `Access-Control-Allow-*` headers
+/// instead of reaching the inner handler.
+///
+/// # Example
+///
+/// ```
+/// use lang_handler::{CorsHandler, CorsPolicy, Handler, Request, Response};
+///
+/// struct Api;
+///
+/// impl Handler for Api {
+///   type Error = String;
+///
+///   fn handle(&self, _request: Request) -> Result<Response, Self::Error> {
+///     Ok(Response::builder().status(200).body("ok").build())
+///   }
+/// }
+///
+/// let policy = CorsPolicy::new().allow_origin("https://example.com");
+/// let handler = CorsHandler::new(Api, policy);
+///
+/// let preflight = Request::builder()
+///   .method("OPTIONS")
+///   .url("http://example.com/api")
+///   .header("Origin", "https://example.com")
+///   .header("Access-Control-Request-Method", "POST")
+///   .build()
+///   .expect("should build request");
+///
+/// let response = handler.handle(preflight).expect("should handle request");
+/// assert_eq!(response.status(), 204);
+/// assert_eq!(
+///   response.headers().get("Access-Control-Allow-Origin"),
+///   Some("https://example.com".to_string())
+/// );
+/// ```
+pub struct CorsHandler<H: Handler> {
+  handler: H,
+  policy: CorsPolicy,
+  scope: Option<(Box<dyn Condition>, PathBuf)>,
+}
+
+impl<H: Handler> CorsHandler<H> {
+  /// Wraps `handler` with CORS support driven by `policy`.
+  pub fn new(handler: H, policy: CorsPolicy) -> Self {
+    Self {
+      handler,
+      policy,
+      scope: None,
+    }
+  }
+
+  /// Restricts CORS handling to requests matching `condition` (evaluated
+  /// against `docroot`), e.g. to scope CORS to an `/api` prefix. Requests
+  /// that don't match pass straight through to the inner handler.
+  pub fn scoped_to<C, P>(mut self, condition: Box<C>, docroot: P) -> Self
+  where
+    C: Condition + 'static,
+    P: Into<PathBuf>,
+  {
+    self.scope = Some((condition as Box<dyn Condition>, docroot.into()));
+    self
+  }
+
+  fn in_scope(&self, request: &Request) -> bool {
+    match &self.scope {
+      Some((condition, docroot)) => condition.matches(request, docroot).is_some(),
+      None => true,
+    }
+  }
+
+  fn is_preflight(request: &Request) -> bool {
+    request.method() == "OPTIONS" && request.headers().get("Access-Control-Request-Method").is_some()
+  }
+}
+
+impl<H: Handler> Handler for CorsHandler<H> {
+  type Error = H::Error;
+
+  fn handle(&self, request: Request) -> Result<Response, Self::Error> {
+    if !self.in_scope(&request) {
+      return self.handler.handle(request);
+    }
+
+    let Some(origin) = self.policy.matching_origin(&request) else {
+      return self.handler.handle(request);
+    };
+
+    if Self::is_preflight(&request) {
+      let mut builder = Response::builder();
+
+      builder
+        .status(204)
+        .header("Access-Control-Allow-Origin", origin)
+        .header("Access-Control-Allow-Methods", self.policy.allowed_methods.join(", "))
+        .header("Access-Control-Allow-Headers", self.policy.allowed_headers.join(", "));
+
+      if self.policy.allow_credentials {
+        builder.header("Access-Control-Allow-Credentials", "true");
+      }
+
+      return Ok(builder.build());
+    }
+
+    let response = self.handler.handle(request)?;
+    let mut builder = response.extend();
+    builder.header("Access-Control-Allow-Origin", origin);
+
+    if self.policy.allow_credentials {
+      builder.header("Access-Control-Allow-Credentials", "true");
+    }
+
+    Ok(builder.build())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::ResponseBuilder;
+
+  struct FixedHandler;
+
+  impl Handler for FixedHandler {
+    type Error = String;
+
+    fn handle(&self, _request: Request) -> Result<Response, Self::Error> {
+      Ok(ResponseBuilder::new().status(200).body("ok").build())
+    }
+  }
+
+  #[test]
+  fn passes_through_without_origin_header() {
+    let policy = CorsPolicy::new().allow_origin("https://example.com");
+    let handler = CorsHandler::new(FixedHandler, policy);
+
+    let request = Request::builder()
+      .url("http://example.com/api")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("Access-Control-Allow-Origin"), None);
+  }
+
+  #[test]
+  fn echoes_back_matching_origin_on_normal_requests() {
+    let policy = CorsPolicy::new().allow_any_origin(true);
+    let handler = CorsHandler::new(FixedHandler, policy);
+
+    let request = Request::builder()
+      .url("http://example.com/api")
+      .header("Origin", "https://app.example.com")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+      response.headers().get("Access-Control-Allow-Origin"),
+      Some("https://app.example.com".to_string())
+    );
+  }
+
+  #[test]
+  fn short_circuits_preflight_with_204() {
+    let policy = CorsPolicy::new()
+      .allow_origin("https://example.com")
+      .allow_methods(["GET", "POST"])
+      .allow_headers(["Content-Type"]);
+
+    let handler = CorsHandler::new(FixedHandler, policy);
+
+    let request = Request::builder()
+      .method("OPTIONS")
+      .url("http://example.com/api")
+      .header("Origin", "https://example.com")
+      .header("Access-Control-Request-Method", "POST")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.status(), 204);
+    assert_eq!(response.body(), "");
+    assert_eq!(
+      response.headers().get("Access-Control-Allow-Methods"),
+      Some("GET, POST".to_string())
+    );
+  }
+
+  #[test]
+  fn rejects_unlisted_origin() {
+    let policy = CorsPolicy::new().allow_origin("https://example.com");
+    let handler = CorsHandler::new(FixedHandler, policy);
+
+    let request = Request::builder()
+      .url("http://example.com/api")
+      .header("Origin", "https://evil.example")
+      .build()
+      .expect("should build request");
+
+    let response = handler.handle(request).expect("should handle request");
+    assert_eq!(response.headers().get("Access-Control-Allow-Origin"), None);
+  }
+}
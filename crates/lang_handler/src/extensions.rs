@@ -0,0 +1,157 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Object-safe helper trait implemented for every `Clone + Send + Sync`
+/// type, so a type-erased value stored in [`Extensions`] can still be
+/// cloned when the [`Request`](crate::Request) it's attached to is cloned
+/// or extended.
+trait CloneAny: Any + Send + Sync {
+  fn clone_box(&self) -> Box<dyn CloneAny>;
+  fn as_any(&self) -> &dyn Any;
+  fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any + Clone + Send + Sync> CloneAny for T {
+  fn clone_box(&self) -> Box<dyn CloneAny> {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+/// A type-keyed map for attaching out-of-band state to a [`Request`](crate::Request).
+///
+/// Unlike [`attributes`](crate::Request::attributes), which is a string-keyed
+/// bag for values that need to cross process or language boundaries (e.g.
+/// named route captures), `Extensions` holds one value per Rust type and
+/// never leaves the process - a resolved virtual host, an auth principal, or
+/// a "this was already redirected" marker a [`Rewriter`](crate::rewrite::Rewriter)
+/// wants a later stage to see without recomputing it. Mirrors `http-types`'
+/// `Extensions`, except stored values must be `Clone` so that the map
+/// itself can be cloned along with the `Request` it's attached to, e.g. via
+/// `Request::extend`.
+#[derive(Default)]
+pub struct Extensions(HashMap<TypeId, Box<dyn CloneAny>>);
+
+impl Extensions {
+  /// Creates an empty `Extensions` map.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Extensions;
+  ///
+  /// let extensions = Extensions::new();
+  /// assert_eq!(extensions.get::<u32>(), None);
+  /// ```
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Inserts a value into the map, returning the previous value of the same
+  /// type, if any.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Extensions;
+  ///
+  /// let mut extensions = Extensions::new();
+  /// assert_eq!(extensions.insert(5i32), None);
+  /// assert_eq!(extensions.insert(6i32), Some(5i32));
+  /// ```
+  pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+    self
+      .0
+      .insert(TypeId::of::<T>(), Box::new(value))
+      .and_then(|boxed| boxed.as_any().downcast_ref::<T>().cloned())
+  }
+
+  /// Returns a reference to the value of the given type, if present.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Extensions;
+  ///
+  /// let mut extensions = Extensions::new();
+  /// extensions.insert("hello".to_string());
+  /// assert_eq!(extensions.get::<String>(), Some(&"hello".to_string()));
+  /// assert_eq!(extensions.get::<u32>(), None);
+  /// ```
+  pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<&T> {
+    self
+      .0
+      .get(&TypeId::of::<T>())
+      .and_then(|boxed| boxed.as_any().downcast_ref::<T>())
+  }
+
+  /// Returns a mutable reference to the value of the given type, if present.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Extensions;
+  ///
+  /// let mut extensions = Extensions::new();
+  /// extensions.insert(5i32);
+  ///
+  /// if let Some(value) = extensions.get_mut::<i32>() {
+  ///   *value += 1;
+  /// }
+  ///
+  /// assert_eq!(extensions.get::<i32>(), Some(&6));
+  /// ```
+  pub fn get_mut<T: Clone + Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+    self
+      .0
+      .get_mut(&TypeId::of::<T>())
+      .and_then(|boxed| boxed.as_any_mut().downcast_mut::<T>())
+  }
+
+  /// Removes and returns the value of the given type, if present.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Extensions;
+  ///
+  /// let mut extensions = Extensions::new();
+  /// extensions.insert(5i32);
+  /// assert_eq!(extensions.remove::<i32>(), Some(5));
+  /// assert_eq!(extensions.get::<i32>(), None);
+  /// ```
+  pub fn remove<T: Clone + Send + Sync + 'static>(&mut self) -> Option<T> {
+    self
+      .0
+      .remove(&TypeId::of::<T>())
+      .and_then(|boxed| boxed.as_any().downcast_ref::<T>().cloned())
+  }
+}
+
+impl Clone for Extensions {
+  fn clone(&self) -> Self {
+    Self(
+      self
+        .0
+        .iter()
+        .map(|(id, value)| (*id, value.clone_box()))
+        .collect(),
+    )
+  }
+}
+
+impl fmt::Debug for Extensions {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Extensions")
+      .field("len", &self.0.len())
+      .finish()
+  }
+}
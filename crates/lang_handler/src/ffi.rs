@@ -2,7 +2,7 @@ use std::{ffi, ffi::{CStr, CString, c_char}};
 
 use bytes::{Buf, BufMut};
 
-use crate::{Headers, Request, RequestBuilder, Response, ResponseBuilder, Url};
+use crate::{domain_to_unicode, is_valid_json, parse_cookie_header, percent_decode, percent_encode, Cookie, EncodeSet, Headers, Request, RequestBuilder, Response, ResponseBuilder, SameSite, Url};
 
 /// Reclaim a string allocated by the library.
 ///
@@ -182,6 +182,46 @@ pub extern "C" fn lh_headers_set(headers: *mut lh_headers_t, key: *const std::os
     headers.inner.set(key, value);
 }
 
+/// Get the number of distinct header names present in the map.
+///
+/// # Examples
+///
+/// ```c
+/// lh_headers_t* headers = lh_headers_new();
+/// lh_headers_set(headers, "Accept", "application/json");
+/// size_t count = lh_headers_names_count(headers);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_headers_names_count(headers: *const lh_headers_t) -> usize {
+    let headers = unsafe {
+        assert!(!headers.is_null());
+        &*headers
+    };
+    headers.inner.len()
+}
+
+/// Get the name of the nth header in the map, preserving the casing it was
+/// set or added with. Returns NULL if `index` is out of range.
+///
+/// # Examples
+///
+/// ```c
+/// lh_headers_t* headers = lh_headers_new();
+/// lh_headers_set(headers, "Accept", "application/json");
+/// const char* name = lh_headers_name_nth(headers, 0);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_headers_name_nth(headers: *const lh_headers_t, index: usize) -> *const std::os::raw::c_char {
+    let headers = unsafe {
+        assert!(!headers.is_null());
+        &*headers
+    };
+    match headers.inner.iter().nth(index) {
+        Some((name, _)) => CString::new(name.clone()).unwrap().into_raw(),
+        None => std::ptr::null(),
+    }
+}
+
 /// An HTTP request. Includes method, URL, headers, and body.
 #[allow(non_camel_case_types)]
 pub struct lh_request_t {
@@ -229,6 +269,36 @@ pub extern "C" fn lh_request_new(
     Box::into_raw(Box::new(request.into()))
 }
 
+/// Create a new `lh_request_t` with a binary-safe body, given as a pointer
+/// plus explicit length rather than a NUL-terminated string.
+///
+/// # Examples
+///
+/// ```c
+/// uint8_t body[] = { 0x68, 0x00, 0x69 };
+/// lh_request_t* request = lh_request_new_bytes("GET", "https://example.com", headers, body, 3);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_new_bytes(
+    method: *const ffi::c_char,
+    url: *const ffi::c_char,
+    headers: *mut lh_headers_t,
+    body: *const u8,
+    body_len: usize,
+) -> *mut lh_request_t {
+    let method = unsafe { CStr::from_ptr(method).to_string_lossy().into_owned() };
+    let url_str = unsafe { CStr::from_ptr(url).to_string_lossy().into_owned() };
+    let url = Url::parse(&url_str).unwrap();
+    let body = if body.is_null() {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(body, body_len) }
+    };
+    let headers = unsafe { &*headers };
+    let request = Request::new(method, url, headers.into(), body);
+    Box::into_raw(Box::new(request.into()))
+}
+
 /// Free a `lh_request_t`.
 ///
 /// # Examples
@@ -291,7 +361,11 @@ pub extern "C" fn lh_request_headers(request: *const lh_request_t) -> *mut lh_he
     Box::into_raw(Box::new(request.inner.headers().clone().into()))
 }
 
-/// Get the body of the request.
+/// Get the body of the request as a NUL-terminated C string. Returns NULL if
+/// the body contains an interior NUL byte, since a C string can't represent
+/// that losslessly - callers that need binary-safe access, or may see a
+/// non-UTF-8 or NUL-containing body, should use `lh_request_body_bytes`
+/// instead.
 ///
 /// # Examples
 ///
@@ -302,7 +376,32 @@ pub extern "C" fn lh_request_headers(request: *const lh_request_t) -> *mut lh_he
 #[no_mangle]
 pub extern "C" fn lh_request_body(request: *const lh_request_t) -> *const ffi::c_char {
     let request = unsafe { &*request };
-    CString::new(request.inner.body()).unwrap().into_raw()
+    match CString::new(request.inner.body()) {
+        Ok(body) => body.into_raw(),
+        Err(_) => std::ptr::null(),
+    }
+}
+
+/// Get the body of the request as a pointer plus byte length, without
+/// copying and without truncating at interior NUL bytes. The pointer is
+/// valid for as long as `request` is alive.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_t* request = lh_request_new("GET", "https://example.com", headers, "Hello, world!");
+/// const uint8_t* ptr;
+/// size_t len = lh_request_body_bytes(request, &ptr);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_body_bytes(request: *const lh_request_t, out_ptr: *mut *const u8) -> usize {
+    let request = unsafe { &*request };
+    let body = request.inner.body();
+
+    unsafe {
+        *out_ptr = body.as_ptr();
+    }
+    body.len()
 }
 
 /// Read from the body of the request into a buffer. Consumes that many bytes from the body.
@@ -328,6 +427,92 @@ pub extern "C" fn lh_request_body_read(request: *const lh_request_t, buffer: *mu
     length
 }
 
+/// Checks whether the inbound `Content-Type` header of the request denotes
+/// a JSON payload (`application/json`, or any `+json` structured suffix).
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_t* request = lh_request_new("POST", "https://example.com", headers, "{}");
+/// bool is_json = lh_request_is_json(request);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_is_json(request: *const lh_request_t) -> bool {
+    let request = unsafe { &*request };
+    match request.inner.headers().get("Content-Type") {
+        Some(content_type) => {
+            let content_type = content_type.to_ascii_lowercase();
+            let media_type = content_type.split(';').next().unwrap_or("").trim();
+            media_type == "application/json" || media_type.ends_with("+json")
+        }
+        None => false,
+    }
+}
+
+/// Parse the inbound `Cookie` header of the request and return the value of
+/// the named cookie, or NULL if it wasn't sent.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_t* request = lh_request_new("GET", "https://example.com", headers, "");
+/// const char* session = lh_request_cookie(request, "session");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_cookie(request: *const lh_request_t, name: *const ffi::c_char) -> *const ffi::c_char {
+    let request = unsafe { &*request };
+    let name = unsafe {
+        assert!(!name.is_null());
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    };
+
+    match request.inner.headers().get_line("Cookie") {
+        Some(header) => match parse_cookie_header(&header).into_iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => CString::new(value).unwrap().into_raw(),
+            None => std::ptr::null(),
+        },
+        None => std::ptr::null(),
+    }
+}
+
+/// Returns the number of cookies present in the inbound `Cookie` header.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_t* request = lh_request_new("GET", "https://example.com", headers, "");
+/// size_t count = lh_request_cookie_count(request);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_cookie_count(request: *const lh_request_t) -> usize {
+    let request = unsafe { &*request };
+    match request.inner.headers().get_line("Cookie") {
+        Some(header) => parse_cookie_header(&header).len(),
+        None => 0,
+    }
+}
+
+/// Returns the name of the nth cookie in the inbound `Cookie` header, or
+/// NULL if `index` is out of range.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_t* request = lh_request_new("GET", "https://example.com", headers, "");
+/// const char* name = lh_request_cookie_name_nth(request, 0);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_cookie_name_nth(request: *const lh_request_t, index: usize) -> *const ffi::c_char {
+    let request = unsafe { &*request };
+    match request.inner.headers().get_line("Cookie") {
+        Some(header) => match parse_cookie_header(&header).get(index) {
+            Some((name, _)) => CString::new(name.clone()).unwrap().into_raw(),
+            None => std::ptr::null(),
+        },
+        None => std::ptr::null(),
+    }
+}
+
 /// An HTTP request builder. Includes method, URL, headers, and body.
 ///
 /// # Examples
@@ -474,6 +659,56 @@ pub extern "C" fn lh_request_builder_body(
     Box::into_raw(Box::new(builder.inner.clone().body(body).into()))
 }
 
+/// Set the body of the request from a pointer plus explicit length, rather
+/// than a NUL-terminated string, so interior NUL bytes survive intact.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_builder_t* builder = lh_request_builder_new();
+/// uint8_t body[] = { 0x68, 0x00, 0x69 };
+/// lh_request_builder_body_bytes(builder, body, 3);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_builder_body_bytes(
+    builder: *mut lh_request_builder_t,
+    data: *const u8,
+    len: usize,
+) -> *mut lh_request_builder_t {
+    let data = unsafe { std::slice::from_raw_parts(data, len) };
+    let builder = unsafe { &mut *builder };
+    Box::into_raw(Box::new(builder.inner.clone().body(data).into()))
+}
+
+/// Set the body of the request from a UTF-8 JSON string, rejecting it if
+/// it isn't well-formed JSON, and set `Content-Type: application/json` if
+/// no `Content-Type` has been set yet. Returns NULL if `json` is not valid
+/// JSON, leaving the builder untouched.
+///
+/// # Examples
+///
+/// ```c
+/// lh_request_builder_t* builder = lh_request_builder_new();
+/// builder = lh_request_builder_json(builder, "{\"ok\": true}");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_request_builder_json(
+    builder: *mut lh_request_builder_t,
+    json: *const ffi::c_char,
+) -> *mut lh_request_builder_t {
+    let json = unsafe { CStr::from_ptr(json).to_string_lossy().into_owned() };
+    if !is_valid_json(&json) {
+        return std::ptr::null_mut();
+    }
+
+    let builder = unsafe { &mut *builder };
+    let mut inner = builder.inner.clone().body(json);
+    if !inner.headers().has("Content-Type") {
+        inner = inner.header("Content-Type", "application/json");
+    }
+    Box::into_raw(Box::new(inner.into()))
+}
+
 /// Build a `lh_request_t` from a `lh_request_builder_t`.
 ///
 /// # Examples
@@ -495,12 +730,13 @@ pub extern "C" fn lh_request_builder_build(builder: *mut lh_request_builder_t) -
 #[allow(non_camel_case_types)]
 pub struct lh_response_t {
     inner: Response,
+    stream: Option<StreamingBody>,
 }
 
 /// Convert a `Response` into a `lh_response_t`.
 impl From<Response> for lh_response_t {
     fn from(inner: Response) -> Self {
-        Self { inner }
+        Self { inner, stream: None }
     }
 }
 
@@ -573,7 +809,11 @@ pub extern "C" fn lh_response_headers(response: *const lh_response_t) -> *mut lh
     Box::into_raw(Box::new(response.inner.headers().clone().into()))
 }
 
-/// Get the body of the response.
+/// Get the body of the response as a NUL-terminated C string. Returns NULL
+/// if the body contains an interior NUL byte, since a C string can't
+/// represent that losslessly - callers that need binary-safe access, or may
+/// see a non-UTF-8 or NUL-containing body, should use
+/// `lh_response_body_bytes` instead.
 ///
 /// # Examples
 ///
@@ -584,7 +824,71 @@ pub extern "C" fn lh_response_headers(response: *const lh_response_t) -> *mut lh
 #[no_mangle]
 pub extern "C" fn lh_response_body(response: *const lh_response_t) -> *const c_char {
     let response = unsafe { &*response };
-    CString::new(response.inner.body()).unwrap().into_raw()
+    match CString::new(response.inner.body()) {
+        Ok(body) => body.into_raw(),
+        Err(_) => std::ptr::null(),
+    }
+}
+
+/// Get the body of the response as a pointer plus byte length, without
+/// copying and without truncating at interior NUL bytes. The pointer is
+/// valid for as long as `response` is alive.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_t* response = lh_response_new(200, headers, "Hello, world!");
+/// const uint8_t* ptr;
+/// size_t len = lh_response_body_bytes(response, &ptr);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_body_bytes(response: *const lh_response_t, out_ptr: *mut *const u8) -> usize {
+    let response = unsafe { &*response };
+    let body = response.inner.body();
+
+    unsafe {
+        *out_ptr = body.as_ptr();
+    }
+    body.len()
+}
+
+/// Returns whether the response carries a streaming body registered via
+/// `lh_response_builder_body_stream`, rather than a fully buffered one.
+/// Consumers should drive `lh_response_body_stream_read` in a loop instead
+/// of calling `lh_response_body`/`lh_response_body_bytes` when this is true.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_t* response = lh_response_new(200, headers, "Hello, world!");
+/// bool streaming = lh_response_is_streaming(response);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_is_streaming(response: *const lh_response_t) -> bool {
+    let response = unsafe { &*response };
+    response.stream.is_some()
+}
+
+/// Pull the next chunk of a streaming response body into `buf`, writing up
+/// to `cap` bytes and returning the number written. Returns `0` once the
+/// stream is exhausted, or if the response has no streaming body.
+///
+/// # Examples
+///
+/// ```c
+/// uint8_t buf[4096];
+/// size_t written;
+/// while ((written = lh_response_body_stream_read(response, buf, sizeof(buf))) > 0) {
+///   // flush buf[0..written] downstream
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_body_stream_read(response: *mut lh_response_t, buf: *mut u8, cap: usize) -> usize {
+    let response = unsafe { &mut *response };
+    match &response.stream {
+        Some(stream) => (stream.cb)(stream.user_data, buf, cap),
+        None => 0,
+    }
 }
 
 /// An HTTP response builder. Includes status, headers, body, log, and exception string.
@@ -597,12 +901,13 @@ pub extern "C" fn lh_response_body(response: *const lh_response_t) -> *const c_c
 #[allow(non_camel_case_types)]
 pub struct lh_response_builder_t {
     inner: ResponseBuilder,
+    stream: Option<StreamingBody>,
 }
 
 /// Convert a `ResponseBuilder` into a `lh_response_builder_t`.
 impl From<ResponseBuilder> for lh_response_builder_t {
     fn from(inner: ResponseBuilder) -> Self {
-        Self { inner }
+        Self { inner, stream: None }
     }
 }
 
@@ -670,7 +975,7 @@ pub extern "C" fn lh_response_builder_extend(response: *const lh_response_t) ->
 #[no_mangle]
 pub extern "C" fn lh_response_builder_status_code(builder: *mut lh_response_builder_t, status_code: u16) {
     let builder = unsafe { &mut *builder };
-    builder.inner.status(status_code);
+    builder.inner.status(status_code as i32);
 }
 
 /// Add a header to the response.
@@ -704,6 +1009,148 @@ pub extern "C" fn lh_response_builder_body(builder: *mut lh_response_builder_t,
     builder.inner.body(body_str);
 }
 
+/// Set the body of the response from a UTF-8 JSON string, rejecting it if
+/// it isn't well-formed JSON, and set `Content-Type: application/json` if
+/// no `Content-Type` has been set yet. Returns `false` if `json` is not
+/// valid JSON, leaving the builder untouched.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// bool ok = lh_response_builder_json(builder, "{\"ok\": true}");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_json(builder: *mut lh_response_builder_t, json: *const c_char) -> bool {
+    let json = unsafe { CStr::from_ptr(json).to_string_lossy().into_owned() };
+    if !is_valid_json(&json) {
+        return false;
+    }
+
+    let builder = unsafe { &mut *builder };
+    if !builder.inner.headers().has("Content-Type") {
+        builder.inner.header("Content-Type", "application/json");
+    }
+    builder.inner.body(json);
+    true
+}
+
+/// The `SameSite` attribute for a cookie set via `lh_response_builder_cookie_ex`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub enum lh_same_site_t {
+    /// No `SameSite` attribute is emitted.
+    LH_SAME_SITE_NONE_UNSET = 0,
+    /// `SameSite=Strict`.
+    LH_SAME_SITE_STRICT = 1,
+    /// `SameSite=Lax`.
+    LH_SAME_SITE_LAX = 2,
+    /// `SameSite=None`.
+    LH_SAME_SITE_NONE = 3,
+}
+
+/// Extended attributes for a cookie set via `lh_response_builder_cookie_ex`.
+/// Any `*const c_char` field may be NULL to leave that attribute unset, and
+/// `max_age` may be set to `-1` to leave `Max-Age` unset.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct lh_cookie_opts_t {
+    /// The `Path` attribute, or NULL.
+    pub path: *const c_char,
+    /// The `Domain` attribute, or NULL.
+    pub domain: *const c_char,
+    /// The `Max-Age` attribute in seconds, or `-1` to leave it unset.
+    pub max_age: i64,
+    /// A pre-formatted `Expires` HTTP-date, or NULL.
+    pub expires: *const c_char,
+    /// Whether to emit the `Secure` attribute.
+    pub secure: bool,
+    /// Whether to emit the `HttpOnly` attribute.
+    pub http_only: bool,
+    /// The `SameSite` attribute.
+    pub same_site: lh_same_site_t,
+}
+
+/// Add a `Set-Cookie` header to the response with just a name and value.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// lh_response_builder_cookie(builder, "session", "abc123");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_cookie(builder: *mut lh_response_builder_t, name: *const c_char, value: *const c_char) {
+    let builder = unsafe { &mut *builder };
+    let name = unsafe { CStr::from_ptr(name).to_string_lossy().into_owned() };
+    let value = unsafe { CStr::from_ptr(value).to_string_lossy().into_owned() };
+
+    builder.inner.header("Set-Cookie", Cookie::new(name, value).to_string());
+}
+
+/// Add a `Set-Cookie` header to the response with the full set of cookie
+/// attributes carried in `opts`.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// lh_cookie_opts_t opts = {
+///   .path = "/",
+///   .domain = NULL,
+///   .max_age = 3600,
+///   .expires = NULL,
+///   .secure = true,
+///   .http_only = true,
+///   .same_site = LH_SAME_SITE_LAX,
+/// };
+/// lh_response_builder_cookie_ex(builder, "session", "abc123", &opts);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_cookie_ex(
+    builder: *mut lh_response_builder_t,
+    name: *const c_char,
+    value: *const c_char,
+    opts: *const lh_cookie_opts_t,
+) {
+    let builder = unsafe { &mut *builder };
+    let name = unsafe { CStr::from_ptr(name).to_string_lossy().into_owned() };
+    let value = unsafe { CStr::from_ptr(value).to_string_lossy().into_owned() };
+    let opts = unsafe {
+        assert!(!opts.is_null());
+        &*opts
+    };
+
+    let mut cookie = Cookie::new(name, value);
+
+    if !opts.path.is_null() {
+        cookie = cookie.path(unsafe { CStr::from_ptr(opts.path).to_string_lossy().into_owned() });
+    }
+
+    if !opts.domain.is_null() {
+        cookie = cookie.domain(unsafe { CStr::from_ptr(opts.domain).to_string_lossy().into_owned() });
+    }
+
+    if opts.max_age >= 0 {
+        cookie = cookie.max_age(opts.max_age);
+    }
+
+    if !opts.expires.is_null() {
+        cookie = cookie.expires(unsafe { CStr::from_ptr(opts.expires).to_string_lossy().into_owned() });
+    }
+
+    cookie = cookie.secure(opts.secure).http_only(opts.http_only);
+
+    cookie = match opts.same_site {
+        lh_same_site_t::LH_SAME_SITE_STRICT => cookie.same_site(SameSite::Strict),
+        lh_same_site_t::LH_SAME_SITE_LAX => cookie.same_site(SameSite::Lax),
+        lh_same_site_t::LH_SAME_SITE_NONE => cookie.same_site(SameSite::None),
+        lh_same_site_t::LH_SAME_SITE_NONE_UNSET => cookie,
+    };
+
+    builder.inner.header("Set-Cookie", cookie.to_string());
+}
+
 /// Write to the body of the response.
 ///
 /// # Examples
@@ -720,6 +1167,51 @@ pub extern "C" fn lh_response_builder_body_write(builder: *mut lh_response_build
     return len;
 }
 
+/// A pull-based body source, invoked repeatedly by the runtime to produce
+/// the next chunk of a streaming response body. Writes up to `cap` bytes
+/// into `buf` and returns the number of bytes written; returning `0`
+/// signals end of stream.
+#[allow(non_camel_case_types)]
+pub type lh_body_pull_fn = extern "C" fn(user_data: *mut ffi::c_void, buf: *mut u8, cap: usize) -> usize;
+
+/// A streaming body source: a pull callback plus the opaque user data it
+/// was registered with.
+#[derive(Clone, Copy)]
+struct StreamingBody {
+    cb: lh_body_pull_fn,
+    user_data: *mut ffi::c_void,
+}
+
+// SAFETY: The callback and its user_data pointer are supplied by the FFI
+// caller, who is responsible for ensuring they can be invoked from
+// whichever thread drives the pull loop.
+unsafe impl Send for StreamingBody {}
+
+/// Register a pull-callback body source on the response builder, so large
+/// or indefinite-length bodies (file downloads, server-sent events) can be
+/// flushed incrementally instead of fully materialized in memory first.
+///
+/// # Examples
+///
+/// ```c
+/// size_t pull(void* user_data, uint8_t* buf, size_t cap) {
+///   // Write up to `cap` bytes into `buf`, returning 0 once exhausted.
+///   return 0;
+/// }
+///
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// lh_response_builder_body_stream(builder, pull, NULL);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_body_stream(
+    builder: *mut lh_response_builder_t,
+    cb: lh_body_pull_fn,
+    user_data: *mut ffi::c_void,
+) {
+    let builder = unsafe { &mut *builder };
+    builder.stream = Some(StreamingBody { cb, user_data });
+}
+
 /// Write to the log of the response.
 ///
 /// # Examples
@@ -752,6 +1244,80 @@ pub extern "C" fn lh_response_builder_exception(builder: *mut lh_response_builde
     builder.inner.exception(exception_str);
 }
 
+/// The connection disposition of a response: whether the underlying
+/// transport should close, keep the connection alive for reuse, or hand
+/// the socket over to the application for a protocol upgrade.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum lh_connection_type_t {
+    /// Close the connection once the response is sent.
+    LH_CONNECTION_CLOSE = 0,
+    /// Keep the connection alive for subsequent requests.
+    LH_CONNECTION_KEEP_ALIVE = 1,
+    /// Hand the socket back to the application after the response, per the
+    /// `Upgrade` header set via `lh_response_builder_upgrade`.
+    LH_CONNECTION_UPGRADE = 2,
+}
+
+/// Set the connection disposition of the response by writing the
+/// corresponding `Connection` header.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// lh_response_builder_connection(builder, LH_CONNECTION_KEEP_ALIVE);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_connection(builder: *mut lh_response_builder_t, connection: lh_connection_type_t) {
+    let builder = unsafe { &mut *builder };
+    let value = match connection {
+        lh_connection_type_t::LH_CONNECTION_CLOSE => "close",
+        lh_connection_type_t::LH_CONNECTION_KEEP_ALIVE => "keep-alive",
+        lh_connection_type_t::LH_CONNECTION_UPGRADE => "upgrade",
+    };
+    builder.inner.header("Connection", value);
+}
+
+/// Mark the response as a protocol upgrade, writing `Connection: upgrade`
+/// and `Upgrade: <protocol>` so the host transport hands the socket back to
+/// the application instead of closing it after the body.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_builder_t* builder = lh_response_builder_new();
+/// lh_response_builder_upgrade(builder, "websocket");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_builder_upgrade(builder: *mut lh_response_builder_t, protocol: *const c_char) {
+    let builder = unsafe { &mut *builder };
+    let protocol = unsafe { CStr::from_ptr(protocol).to_string_lossy().into_owned() };
+    builder.inner.header("Connection", "upgrade");
+    builder.inner.header("Upgrade", protocol);
+}
+
+/// Read back the connection disposition of the response from its
+/// `Connection` header. Defaults to `LH_CONNECTION_KEEP_ALIVE` when the
+/// header is absent or unrecognized.
+///
+/// # Examples
+///
+/// ```c
+/// lh_response_t* response = lh_response_new(200, headers, "Hello, world!");
+/// lh_connection_type_t connection = lh_response_connection(response);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_response_connection(response: *const lh_response_t) -> lh_connection_type_t {
+    let response = unsafe { &*response };
+    match response.inner.headers().get("Connection").map(|v| v.to_ascii_lowercase()) {
+        Some(ref value) if value == "upgrade" => lh_connection_type_t::LH_CONNECTION_UPGRADE,
+        Some(ref value) if value == "close" => lh_connection_type_t::LH_CONNECTION_CLOSE,
+        _ => lh_connection_type_t::LH_CONNECTION_KEEP_ALIVE,
+    }
+}
+
 /// Build a `lh_response_t` from a `lh_response_builder_t`.
 ///
 /// # Examples
@@ -766,7 +1332,9 @@ pub extern "C" fn lh_response_builder_exception(builder: *mut lh_response_builde
 #[no_mangle]
 pub extern "C" fn lh_response_builder_build(builder: *const lh_response_builder_t) -> *mut lh_response_t {
     let builder = unsafe { &*builder };
-    Box::into_raw(Box::new(builder.inner.build().into()))
+    let mut response: lh_response_t = builder.inner.build().into();
+    response.stream = builder.stream;
+    Box::into_raw(Box::new(response))
 }
 
 /// An HTTP URL. Includes scheme, host, port, domain, origin, authority, username, password, path, query, fragment, and URI.
@@ -809,6 +1377,52 @@ pub extern "C" fn lh_url_parse(url: *const c_char) -> *mut lh_url_t {
     Box::into_raw(Box::new(url.into()))
 }
 
+/// Resolve a possibly-relative reference against a base URL, following the
+/// WHATWG URL "relative resolution" algorithm (e.g. `base.join("../x")`).
+/// Returns NULL if `relative` can't be resolved against `base`.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* base = lh_url_parse("https://example.com/foo/bar?x");
+/// lh_url_t* resolved = lh_url_join(base, "../baz");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_join(base: *const lh_url_t, relative: *const c_char) -> *mut lh_url_t {
+    let base = unsafe { &*base };
+    let relative = unsafe { CStr::from_ptr(relative).to_string_lossy().into_owned() };
+
+    match base.inner.join(&relative) {
+        Ok(url) => Box::into_raw(Box::new(url.into())),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Parse `base` as an absolute URL, then resolve `input` against it as a
+/// possibly-relative reference. Returns NULL if either `base` fails to
+/// parse or `input` can't be resolved against it.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* resolved = lh_url_parse_with_base("https://example.com/foo/bar?x", "../baz");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_parse_with_base(base: *const c_char, input: *const c_char) -> *mut lh_url_t {
+    let base = unsafe { CStr::from_ptr(base).to_string_lossy().into_owned() };
+    let input = unsafe { CStr::from_ptr(input).to_string_lossy().into_owned() };
+
+    let base_url = match Url::parse(&base) {
+        Ok(url) => url,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match base_url.join(&input) {
+        Ok(url) => Box::into_raw(Box::new(url.into())),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
 /// Free a `lh_url_t`.
 ///
 /// # Examples
@@ -871,6 +1485,38 @@ pub extern "C" fn lh_url_port(url: *const lh_url_t) -> u16 {
     url.inner.port().unwrap_or(0)
 }
 
+/// Get the port of the URL, falling back to the scheme's well-known default
+/// (e.g. 80 for `http`/`ws`, 443 for `https`/`wss`, 21 for `ftp`) when none
+/// is explicit. Returns 0 if the scheme has no known default and no port
+/// was given.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://example.com/path");
+/// uint16_t port = lh_url_port_or_default(url); // 443
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_port_or_default(url: *const lh_url_t) -> u16 {
+    let url = unsafe { &*url };
+    url.inner.port_or_known_default().unwrap_or(0)
+}
+
+/// Returns `true` if the URL has an explicit port, as opposed to relying on
+/// the scheme's default.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://example.com:8443/path");
+/// bool explicit = lh_url_has_explicit_port(url); // true
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_has_explicit_port(url: *const lh_url_t) -> bool {
+    let url = unsafe { &*url };
+    url.inner.port().is_some()
+}
+
 /// Get the domain of the URL.
 ///
 /// # Examples
@@ -885,6 +1531,55 @@ pub extern "C" fn lh_url_domain(url: *const lh_url_t) -> *const c_char {
     CString::new(url.inner.domain().unwrap_or("")).unwrap().into_raw()
 }
 
+/// The kind of host a URL's authority carries, mirroring `url::Host`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub enum lh_host_type_t {
+    /// No host (e.g. `mailto:` URIs).
+    LH_HOST_NONE = 0,
+    /// A registered domain name, possibly IDNA-encoded (`xn--...`).
+    LH_HOST_DOMAIN = 1,
+    /// An IPv4 address.
+    LH_HOST_IPV4 = 2,
+    /// An IPv6 address.
+    LH_HOST_IPV6 = 3,
+}
+
+/// Get the kind of host carried by the URL's authority.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://127.0.0.1/path");
+/// lh_host_type_t host_type = lh_url_host_type(url); // LH_HOST_IPV4
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_host_type(url: *const lh_url_t) -> lh_host_type_t {
+    let url = unsafe { &*url };
+    match url.inner.host() {
+        Some(url::Host::Domain(_)) => lh_host_type_t::LH_HOST_DOMAIN,
+        Some(url::Host::Ipv4(_)) => lh_host_type_t::LH_HOST_IPV4,
+        Some(url::Host::Ipv6(_)) => lh_host_type_t::LH_HOST_IPV6,
+        None => lh_host_type_t::LH_HOST_NONE,
+    }
+}
+
+/// Get the Unicode presentation form of the URL's host, decoding any
+/// `xn--` IDNA labels. Non-domain hosts (IPv4, IPv6) are returned as-is.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://xn--mnchen-3ya.de/path");
+/// const char* host = lh_url_host_unicode(url); // "münchen.de"
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_host_unicode(url: *const lh_url_t) -> *const c_char {
+    let url = unsafe { &*url };
+    let host = url.inner.host_str().unwrap_or("");
+    CString::new(domain_to_unicode(host)).unwrap().into_raw()
+}
+
 /// Get the origin of the URL.
 ///
 /// # Examples
@@ -1018,3 +1713,258 @@ pub extern "C" fn lh_url_uri(url: *const lh_url_t) -> *const c_char {
     let url = unsafe { &*url };
     CString::new(url.inner.as_str()).unwrap().into_raw()
 }
+
+/// Set the scheme of the URL. Returns `false` if the scheme is invalid for
+/// this URL (e.g. switching to/from a scheme with different special-casing
+/// rules, per the WHATWG URL spec).
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// bool ok = lh_url_set_scheme(url, "https");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_scheme(url: *mut lh_url_t, scheme: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let scheme = unsafe { CStr::from_ptr(scheme).to_string_lossy().into_owned() };
+    url.inner.set_scheme(&scheme).is_ok()
+}
+
+/// Set the host of the URL. Returns `false` if the host is invalid, or if
+/// this URL cannot have a host (e.g. `data:` URLs).
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// bool ok = lh_url_set_host(url, "example.org");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_host(url: *mut lh_url_t, host: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let host = unsafe { CStr::from_ptr(host).to_string_lossy().into_owned() };
+    url.inner.set_host(Some(&host)).is_ok()
+}
+
+/// Set the port of the URL. Returns `false` if this URL cannot have a port
+/// (e.g. it has no host, or is a scheme like `file:` that forbids ports).
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// bool ok = lh_url_set_port(url, 8080);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_port(url: *mut lh_url_t, port: u16) -> bool {
+    let url = unsafe { &mut *url };
+    url.inner.set_port(Some(port)).is_ok()
+}
+
+/// Set the path of the URL.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// lh_url_set_path(url, "/path/to/resource");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_path(url: *mut lh_url_t, path: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let path = unsafe { CStr::from_ptr(path).to_string_lossy().into_owned() };
+    url.inner.set_path(&path);
+    true
+}
+
+/// Set the query string of the URL. Pass an empty string to clear it.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// lh_url_set_query(url, "a=1&b=2");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_query(url: *mut lh_url_t, query: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let query = unsafe { CStr::from_ptr(query).to_string_lossy().into_owned() };
+    url.inner.set_query(if query.is_empty() { None } else { Some(&query) });
+    true
+}
+
+/// Set the fragment of the URL. Pass an empty string to clear it.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// lh_url_set_fragment(url, "section");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_fragment(url: *mut lh_url_t, fragment: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let fragment = unsafe { CStr::from_ptr(fragment).to_string_lossy().into_owned() };
+    url
+        .inner
+        .set_fragment(if fragment.is_empty() { None } else { Some(&fragment) });
+    true
+}
+
+/// Set the username of the URL. Returns `false` if this URL cannot have a
+/// username (e.g. it has no host).
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// bool ok = lh_url_set_username(url, "alice");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_username(url: *mut lh_url_t, username: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let username = unsafe { CStr::from_ptr(username).to_string_lossy().into_owned() };
+    url.inner.set_username(&username).is_ok()
+}
+
+/// Set the password of the URL. Returns `false` if this URL cannot have a
+/// password (e.g. it has no host). Pass an empty string to clear it.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("http://example.com");
+/// bool ok = lh_url_set_password(url, "hunter2");
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_set_password(url: *mut lh_url_t, password: *const c_char) -> bool {
+    let url = unsafe { &mut *url };
+    let password = unsafe { CStr::from_ptr(password).to_string_lossy().into_owned() };
+    url
+        .inner
+        .set_password(if password.is_empty() { None } else { Some(&password) })
+        .is_ok()
+}
+
+/// Count the number of key/value pairs in the URL's query string, as parsed
+/// by `application/x-www-form-urlencoded` rules. Useful for pre-sizing an
+/// array before calling [`lh_url_query_pair`] for each index.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://example.com/search?q=rust&lang=en");
+/// size_t count = lh_url_query_pairs_count(url);
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_query_pairs_count(url: *const lh_url_t) -> usize {
+    let url = unsafe { &*url };
+    url.inner.query_pairs().count()
+}
+
+/// Get the percent-decoded key and value of the query pair at `index`,
+/// writing newly allocated, NUL-terminated strings into `out_key` and
+/// `out_value`. Returns `false` (leaving `out_key`/`out_value` untouched) if
+/// `index` is out of range, or if the decoded key or value contains an
+/// interior NUL byte (e.g. from `?a=%00`) and so can't be represented as a
+/// C string.
+///
+/// # Examples
+///
+/// ```c
+/// lh_url_t* url = lh_url_parse("https://example.com/search?q=rust%20lang");
+///
+/// char* key = NULL;
+/// char* value = NULL;
+/// if (lh_url_query_pair(url, 0, &key, &value)) {
+///   // key == "q", value == "rust lang"
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_query_pair(
+    url: *const lh_url_t,
+    index: usize,
+    out_key: *mut *mut c_char,
+    out_value: *mut *mut c_char,
+) -> bool {
+    let url = unsafe { &*url };
+
+    match url.inner.query_pairs().nth(index) {
+        Some((key, value)) => {
+            let (key, value) = match (CString::new(key.into_owned()), CString::new(value.into_owned())) {
+                (Ok(key), Ok(value)) => (key, value),
+                _ => return false,
+            };
+            unsafe {
+                *out_key = key.into_raw();
+                *out_value = value.into_raw();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Which part of a URI a percent-encoding call is destined for, selecting
+/// which delimiter characters must be escaped. See [`EncodeSet`].
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub enum lh_url_encode_set_t {
+    /// Escape everything except unreserved characters (`A-Za-z0-9-._~`).
+    LH_URL_ENCODE_COMPONENT = 0,
+    /// Leave path sub-delimiters, `:`/`@`, and `/` unescaped.
+    LH_URL_ENCODE_PATH = 1,
+    /// Leave sub-delimiters, `:`/`@`, and `/`/`?` unescaped.
+    LH_URL_ENCODE_QUERY = 2,
+    /// Same safe set as `LH_URL_ENCODE_QUERY`.
+    LH_URL_ENCODE_FRAGMENT = 3,
+    /// Leave sub-delimiters unescaped, for userinfo.
+    LH_URL_ENCODE_USERINFO = 4,
+}
+
+impl From<lh_url_encode_set_t> for EncodeSet {
+    fn from(set: lh_url_encode_set_t) -> EncodeSet {
+        match set {
+            lh_url_encode_set_t::LH_URL_ENCODE_COMPONENT => EncodeSet::Component,
+            lh_url_encode_set_t::LH_URL_ENCODE_PATH => EncodeSet::Path,
+            lh_url_encode_set_t::LH_URL_ENCODE_QUERY => EncodeSet::Query,
+            lh_url_encode_set_t::LH_URL_ENCODE_FRAGMENT => EncodeSet::Fragment,
+            lh_url_encode_set_t::LH_URL_ENCODE_USERINFO => EncodeSet::UserInfo,
+        }
+    }
+}
+
+/// Percent-encode `input` for the given `component`, returning a newly
+/// allocated, NUL-terminated string.
+///
+/// # Examples
+///
+/// ```c
+/// char* encoded = lh_url_percent_encode("a b/c", LH_URL_ENCODE_COMPONENT);
+/// // encoded == "a%20b%2Fc"
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_percent_encode(input: *const c_char, component: lh_url_encode_set_t) -> *mut c_char {
+    let input = unsafe { CStr::from_ptr(input).to_string_lossy().into_owned() };
+    CString::new(percent_encode(&input, component.into())).unwrap().into_raw()
+}
+
+/// Percent-decode `input`, returning a newly allocated, NUL-terminated
+/// string, or NULL if the decoded result contains an interior NUL byte
+/// (e.g. from `%00`) and so can't be represented as a C string.
+///
+/// # Examples
+///
+/// ```c
+/// char* decoded = lh_url_percent_decode("a%20b%2Fc");
+/// // decoded == "a b/c"
+/// ```
+#[no_mangle]
+pub extern "C" fn lh_url_percent_decode(input: *const c_char) -> *mut c_char {
+    let input = unsafe { CStr::from_ptr(input).to_string_lossy().into_owned() };
+    match CString::new(percent_decode(&input)) {
+        Ok(decoded) => decoded.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
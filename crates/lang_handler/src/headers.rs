@@ -1,4 +1,35 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A non-cryptographic FNV-1a hasher.
+///
+/// Header names are short and numerous, so the SipHash used by `std`'s
+/// default hasher (built for DoS resistance against attacker-controlled
+/// keys from untrusted sources like HTTP bodies) is overkill here; FNV-1a
+/// trades that resistance for speed, the same tradeoff the `http` crate's
+/// `HeaderMap` benchmarks explore with fnv/seahash.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+  fn default() -> Self {
+    FnvHasher(0xcbf29ce484222325)
+  }
+}
+
+impl Hasher for FnvHasher {
+  fn finish(&self) -> u64 {
+    self.0
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    for byte in bytes {
+      self.0 ^= *byte as u64;
+      self.0 = self.0.wrapping_mul(0x100000001b3);
+    }
+  }
+}
+
+type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
 
 /// Represents a single HTTP header value or multiple values for the same header.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -19,6 +50,21 @@ impl From<&Header> for String {
   }
 }
 
+/// Header names whose multiple values must never be comma-joined into a
+/// single line. Each of these carries a value that may itself legally
+/// contain commas (an expiry date, a credential challenge, a cookie
+/// attribute list), so joining with `,` the way [`Header::Multiple`]
+/// normally is produces an ambiguous or outright corrupt value - per
+/// RFC 6265 §3 and RFC 7230 §3.2.2, these must always be sent as separate
+/// header lines.
+const NEVER_COMBINE: &[&str] = &["set-cookie", "www-authenticate", "proxy-authenticate"];
+
+/// Whether `name` (compared case-insensitively) must never have its values
+/// combined into a single comma-joined line - see [`NEVER_COMBINE`].
+pub(crate) fn is_never_combine<K: AsRef<str>>(name: K) -> bool {
+  NEVER_COMBINE.contains(&name.as_ref().to_lowercase().as_str())
+}
+
 // TODO: Figure out why From<Into<String>> conflicts with From<Vec<String>>
 impl From<String> for Header {
   fn from(value: String) -> Header {
@@ -91,7 +137,15 @@ mod napi_header {
 /// assert_eq!(headers.get("Content-Type"), Some("text/plain".to_string()));
 /// ```
 #[derive(Debug, Clone)]
-pub struct Headers(HashMap<String, Header>);
+pub struct Headers {
+  /// Entries in insertion order, each keeping the casing of the name as
+  /// first seen.
+  entries: Vec<(String, Header)>,
+
+  /// Maps a lowercased header name to its index in `entries`, for
+  /// case-insensitive lookup without scanning.
+  index: HashMap<String, usize, FnvBuildHasher>,
+}
 
 impl Headers {
   /// Creates a new `Headers` instance.
@@ -103,7 +157,10 @@ impl Headers {
   /// let headers = Headers::new();
   /// ```
   pub fn new() -> Self {
-    Headers(HashMap::new())
+    Headers {
+      entries: Vec::new(),
+      index: HashMap::default(),
+    }
   }
 
   /// Checks if a header field exists.
@@ -122,7 +179,7 @@ impl Headers {
   where
     K: AsRef<str>,
   {
-    self.0.contains_key(key.as_ref().to_lowercase().as_str())
+    self.index.contains_key(key.as_ref().to_lowercase().as_str())
   }
 
   /// Returns the last single value associated with a header field.
@@ -141,9 +198,9 @@ impl Headers {
   where
     K: AsRef<str>,
   {
-    match self.0.get(key.as_ref().to_lowercase().as_str()) {
-      Some(Header::Single(value)) => Some(value.clone()),
-      Some(Header::Multiple(values)) => values.last().cloned(),
+    match self.entry_for(key) {
+      Some((_, Header::Single(value))) => Some(value.clone()),
+      Some((_, Header::Multiple(values))) => values.last().cloned(),
       None => None,
     }
   }
@@ -170,13 +227,31 @@ impl Headers {
   where
     K: AsRef<str>,
   {
-    match self.0.get(key.as_ref().to_lowercase().as_str()) {
-      Some(Header::Single(value)) => vec![value.clone()],
-      Some(Header::Multiple(values)) => values.clone(),
+    match self.entry_for(key) {
+      Some((_, Header::Single(value))) => vec![value.clone()],
+      Some((_, Header::Multiple(values))) => values.clone(),
       None => Vec::new(),
     }
   }
 
+  /// Returns every `Set-Cookie` value, as a convenience over `get_all` for
+  /// the one header where callers should always assume multiple values and
+  /// never join them onto one line.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.add("Set-Cookie", "a=1");
+  /// headers.add("Set-Cookie", "b=2");
+  ///
+  /// assert_eq!(headers.get_set_cookies(), vec!["a=1".to_string(), "b=2".to_string()]);
+  /// ```
+  pub fn get_set_cookies(&self) -> Vec<String> {
+    self.get_all("Set-Cookie")
+  }
+
   /// Returns all values associated with a header field as a single
   /// comma-separated string.
   ///
@@ -185,7 +260,9 @@ impl Headers {
   /// Some headers support delivery as a comma-separated list of values,
   /// but most require multiple header lines to send multiple values.
   /// Typically you should use `get_all` rather than `get_line` and send
-  /// multiple header lines.
+  /// multiple header lines. This is never safe for `Set-Cookie`,
+  /// `WWW-Authenticate`, or `Proxy-Authenticate` - use
+  /// [`Headers::get_set_cookies`] or `get_all` instead.
   ///
   /// # Examples
   ///
@@ -201,10 +278,125 @@ impl Headers {
   where
     K: AsRef<str>,
   {
-    self
-      .0
-      .get(key.as_ref().to_lowercase().as_str())
-      .map(|v| v.into())
+    self.entry_for(key).map(|(_, header)| header.into())
+  }
+
+  /// Parses a header field as a typed value via [`FromHeader`], joining
+  /// multiple values the same way [`Headers::get_line`] does before
+  /// parsing. Returns `None` if the header is absent or doesn't parse.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// use lang_handler::negotiation::ContentType;
+  ///
+  /// let mut headers = Headers::new();
+  /// headers.set("Content-Type", "application/json; charset=utf-8");
+  ///
+  /// let content_type = headers.parse::<ContentType>("Content-Type")
+  ///   .expect("should parse");
+  ///
+  /// assert_eq!(content_type.media_type, "application/json");
+  /// ```
+  pub fn parse<K, T>(&self, key: K) -> Option<T>
+  where
+    K: AsRef<str>,
+    T: crate::negotiation::FromHeader,
+  {
+    self.get_line(key).and_then(|line| T::from_header(&line))
+  }
+
+  /// Parses a header field as a typed value via
+  /// [`FromHeaderValue`](crate::negotiation::FromHeaderValue), returning a
+  /// descriptive error rather than folding "absent" and "malformed" into
+  /// the same `None`, the way [`Headers::parse`] does.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// use lang_handler::negotiation::HeaderValueError;
+  ///
+  /// let mut headers = Headers::new();
+  /// headers.set("Content-Length", "not-a-number");
+  ///
+  /// assert!(matches!(
+  ///   headers.get_typed::<u64, _>("Content-Length"),
+  ///   Err(HeaderValueError::Invalid { .. })
+  /// ));
+  /// ```
+  pub fn get_typed<T, K>(&self, key: K) -> Result<T, crate::negotiation::HeaderValueError>
+  where
+    K: AsRef<str>,
+    T: crate::negotiation::FromHeaderValue,
+  {
+    use crate::negotiation::HeaderValueError;
+
+    let line = self.get_line(key.as_ref()).ok_or(HeaderValueError::Missing)?;
+    T::from_header_value(&line).ok_or_else(|| HeaderValueError::Invalid {
+      name: key.as_ref().to_string(),
+      value: line,
+    })
+  }
+
+  /// Returns the parsed `Content-Type` header, or `None` if it's absent or
+  /// malformed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.set("Content-Type", "application/json; charset=utf-8");
+  ///
+  /// assert_eq!(headers.content_type().unwrap().media_type, "application/json");
+  /// ```
+  pub fn content_type(&self) -> Option<crate::negotiation::ContentType> {
+    self.get_typed("Content-Type").ok()
+  }
+
+  /// Returns the parsed `Content-Length` header, or `None` if it's absent
+  /// or not a valid non-negative integer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.set("Content-Length", "13");
+  ///
+  /// assert_eq!(headers.content_length(), Some(13));
+  /// ```
+  pub fn content_length(&self) -> Option<u64> {
+    self.get_typed("Content-Length").ok()
+  }
+
+  /// Picks the best of `available` according to the `q`-value weighted
+  /// offers in the named header (typically `Accept`, `Accept-Language`, or
+  /// `Accept-Encoding`). A higher quality wins; among ties, a more specific
+  /// match wins (an exact value over a `type/*` wildcard over `*`/`*/*`).
+  /// An offer with `q=0` is never selected. Returns the first of
+  /// `available` if the header is absent, and `None` if `available` is
+  /// empty or every candidate is excluded by an explicit `q=0`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.set("Accept", "text/html;q=0.8, application/json");
+  ///
+  /// assert_eq!(
+  ///   headers.negotiate("Accept", &["text/html", "application/json"]),
+  ///   Some("application/json")
+  /// );
+  /// ```
+  pub fn negotiate<'a, K>(&self, key: K, available: &[&'a str]) -> Option<&'a str>
+  where
+    K: AsRef<str>,
+  {
+    crate::negotiation::negotiate(self.get_line(key).as_deref(), available)
   }
 
   /// Sets a header field, replacing any existing values.
@@ -224,7 +416,16 @@ impl Headers {
     K: Into<String>,
     V: Into<Header>,
   {
-    self.0.insert(key.into().to_lowercase(), value.into());
+    let key = key.into();
+    let lowercase = key.to_lowercase();
+
+    match self.index.get(&lowercase) {
+      Some(&i) => self.entries[i].1 = value.into(),
+      None => {
+        self.index.insert(lowercase, self.entries.len());
+        self.entries.push((key, value.into()));
+      }
+    }
   }
 
   /// Add a header with the given value without replacing existing ones.
@@ -247,27 +448,39 @@ impl Headers {
     K: Into<String>,
     V: Into<String>,
   {
-    let key = key.into().to_lowercase();
-    let value = value.into();
+    self.entry(key).append(value);
+  }
 
-    match self.0.entry(key) {
-      Entry::Vacant(e) => {
-        e.insert(Header::Single(value));
-      }
-      Entry::Occupied(mut e) => {
-        let header = e.get_mut();
-        *header = match header {
-          Header::Single(existing_value) => {
-            let mut values = vec![existing_value.clone()];
-            values.push(value);
-            Header::Multiple(values)
-          }
-          Header::Multiple(values) => {
-            values.push(value);
-            Header::Multiple(values.clone())
-          }
-        };
-      }
+  /// Gets an append-or-insert view of a single header slot, looking it up
+  /// only once rather than once for a `has` check and again for the
+  /// mutation - mirroring `std`'s `HashMap::entry`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.entry("Accept").append("text/plain");
+  /// headers.entry("Accept").append("application/json");
+  ///
+  /// assert_eq!(headers.get_all("Accept"), vec![
+  ///   "text/plain".to_string(),
+  ///   "application/json".to_string()
+  /// ]);
+  /// ```
+  pub fn entry<K>(&mut self, key: K) -> HeaderEntry<'_>
+  where
+    K: Into<String>,
+  {
+    let key = key.into();
+    let lowercase = key.to_lowercase();
+
+    match self.index.get(&lowercase) {
+      Some(&i) => HeaderEntry::Occupied(OccupiedHeaderEntry {
+        headers: self,
+        index: i,
+      }),
+      None => HeaderEntry::Vacant(VacantHeaderEntry { headers: self, key }),
     }
   }
 
@@ -287,7 +500,20 @@ impl Headers {
   where
     K: AsRef<str>,
   {
-    self.0.remove(key.as_ref().to_lowercase().as_str());
+    let lowercase = key.as_ref().to_lowercase();
+
+    let Some(i) = self.index.remove(&lowercase) else {
+      return;
+    };
+
+    self.entries.remove(i);
+
+    // Every entry after the removed one shifted down by one.
+    for index in self.index.values_mut() {
+      if *index > i {
+        *index -= 1;
+      }
+    }
   }
 
   /// Clears all headers.
@@ -305,7 +531,8 @@ impl Headers {
   /// assert_eq!(headers.get("Accept"), None);
   /// ```
   pub fn clear(&mut self) {
-    self.0.clear();
+    self.entries.clear();
+    self.index.clear();
   }
 
   /// Returns the number of headers.
@@ -322,7 +549,7 @@ impl Headers {
   /// assert_eq!(headers.len(), 2);
   /// ```
   pub fn len(&self) -> usize {
-    self.0.len()
+    self.entries.len()
   }
 
   /// Checks if the headers are empty.
@@ -355,14 +582,53 @@ impl Headers {
   /// }
   ///
   /// # assert_eq!(headers.iter().collect::<Vec<(&String, &Header)>>(), vec![
-  /// #   (&"accept".to_string(), &Header::Multiple(vec![
+  /// #   (&"Accept".to_string(), &Header::Multiple(vec![
   /// #     "text/plain".to_string(),
   /// #     "application/json".to_string()
   /// #   ]))
   /// # ]);
   /// ```
   pub fn iter(&self) -> impl Iterator<Item = (&String, &Header)> {
-    self.0.iter()
+    self.entries.iter().map(|(name, header)| (name, header))
+  }
+
+  /// Returns an iterator over `(name, value)` pairs, yielding each value of
+  /// a [`Header::Multiple`] as its own pair rather than grouping them -
+  /// exactly the shape a SAPI or CGI layer needs to stream headers out as
+  /// individual lines.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::Headers;
+  /// let mut headers = Headers::new();
+  /// headers.add("Set-Cookie", "a=1");
+  /// headers.add("Set-Cookie", "b=2");
+  ///
+  /// assert_eq!(headers.iter_lines().collect::<Vec<_>>(), vec![
+  ///   ("Set-Cookie", "a=1"),
+  ///   ("Set-Cookie", "b=2"),
+  /// ]);
+  /// ```
+  pub fn iter_lines(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.entries.iter().flat_map(|(name, header)| {
+      let values: Box<dyn Iterator<Item = &str>> = match header {
+        Header::Single(value) => Box::new(std::iter::once(value.as_str())),
+        Header::Multiple(values) => Box::new(values.iter().map(String::as_str)),
+      };
+      values.map(move |value| (name.as_str(), value))
+    })
+  }
+
+  /// Looks up the stored `(name, header)` entry for `key`, case-insensitively.
+  fn entry_for<K>(&self, key: K) -> Option<&(String, Header)>
+  where
+    K: AsRef<str>,
+  {
+    self
+      .index
+      .get(key.as_ref().to_lowercase().as_str())
+      .map(|&i| &self.entries[i])
   }
 }
 
@@ -372,6 +638,105 @@ impl Default for Headers {
   }
 }
 
+/// An append-or-insert view into a single header slot, returned by
+/// [`Headers::entry`].
+pub enum HeaderEntry<'a> {
+  /// No header with this name exists yet.
+  Vacant(VacantHeaderEntry<'a>),
+
+  /// A header with this name already exists.
+  Occupied(OccupiedHeaderEntry<'a>),
+}
+
+impl HeaderEntry<'_> {
+  /// Appends `value` to this slot: inserted as a new [`Header::Single`] if
+  /// vacant, or pushed onto the existing value - promoting it to a
+  /// [`Header::Multiple`] first if it's still a `Single` - without cloning
+  /// any other header.
+  pub fn append<V: Into<String>>(self, value: V) {
+    match self {
+      HeaderEntry::Vacant(entry) => entry.insert(value),
+      HeaderEntry::Occupied(entry) => entry.append(value),
+    }
+  }
+}
+
+/// A [`HeaderEntry`] for a header name with no existing value.
+pub struct VacantHeaderEntry<'a> {
+  headers: &'a mut Headers,
+  key: String,
+}
+
+impl VacantHeaderEntry<'_> {
+  /// Inserts `value` as this header's sole value.
+  pub fn insert<V: Into<String>>(self, value: V) {
+    let VacantHeaderEntry { headers, key } = self;
+    let lowercase = key.to_lowercase();
+    let index = headers.entries.len();
+
+    headers.index.insert(lowercase, index);
+    headers.entries.push((key, Header::Single(value.into())));
+  }
+}
+
+/// A [`HeaderEntry`] for a header name that already has a value.
+pub struct OccupiedHeaderEntry<'a> {
+  headers: &'a mut Headers,
+  index: usize,
+}
+
+impl OccupiedHeaderEntry<'_> {
+  /// Appends `value` to the existing header, promoting a [`Header::Single`]
+  /// to a [`Header::Multiple`] first if necessary.
+  pub fn append<V: Into<String>>(self, value: V) {
+    let (_, header) = &mut self.headers.entries[self.index];
+    let value = value.into();
+
+    match header {
+      Header::Single(existing) => *header = Header::Multiple(vec![existing.clone(), value]),
+      Header::Multiple(values) => values.push(value),
+    }
+  }
+}
+
+impl IntoIterator for Headers {
+  type Item = (String, Header);
+  type IntoIter = std::vec::IntoIter<(String, Header)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a Headers {
+  type Item = (&'a String, &'a Header);
+  type IntoIter =
+    std::iter::Map<std::slice::Iter<'a, (String, Header)>, fn(&'a (String, Header)) -> (&'a String, &'a Header)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.iter().map(|(name, header)| (name, header))
+  }
+}
+
+impl FromIterator<(String, Header)> for Headers {
+  /// Builds a `Headers` from `(name, header)` pairs, the same shape
+  /// [`Headers`]'s own `IntoIterator` yields - so
+  /// `headers.into_iter().collect::<Headers>()` round-trips.
+  fn from_iter<I: IntoIterator<Item = (String, Header)>>(iter: I) -> Self {
+    let mut headers = Headers::new();
+    headers.extend(iter);
+    headers
+  }
+}
+
+impl Extend<(String, Header)> for Headers {
+  fn extend<I: IntoIterator<Item = (String, Header)>>(&mut self, iter: I) {
+    for (key, header) in iter {
+      self.set(key, header);
+    }
+  }
+}
+
 #[cfg(feature = "napi")]
 mod napi_headers {
   use super::*;
@@ -442,7 +807,19 @@ mod napi_headers {
 
         for (key, header) in value.iter() {
           let key_cstr = std::ffi::CString::new(key.to_string())?;
-          let value_napi_value = Header::to_napi_value(env, header.to_owned())?;
+
+          // A never-combine header (e.g. `Set-Cookie`) always surfaces as
+          // an array, even with a single value, so JS callers can rely on
+          // a stable shape instead of branching on string-vs-array.
+          let value_napi_value = if is_never_combine(key) {
+            let values = match header {
+              Header::Single(value) => vec![value.clone()],
+              Header::Multiple(values) => values.clone(),
+            };
+            Vec::<String>::to_napi_value(env, values)?
+          } else {
+            Header::to_napi_value(env, header.to_owned())?
+          };
 
           check_status!(
             sys::napi_set_named_property(env, result, key_cstr.as_ptr(), value_napi_value),
@@ -0,0 +1,131 @@
+//! A minimal Punycode (RFC 3492) decoder, used to recover the
+//! human-readable Unicode form of an ASCII-compatible ("xn--") domain
+//! label produced by IDNA.
+
+const BASE: u32 = 36;
+const TMIN: u32 = 1;
+const TMAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+  delta /= if first_time { DAMP } else { 2 };
+  delta += delta / num_points;
+
+  let mut k = 0;
+  while delta > ((BASE - TMIN) * TMAX) / 2 {
+    delta /= BASE - TMIN;
+    k += BASE;
+  }
+
+  k + (((BASE - TMIN + 1) * delta) / (delta + SKEW))
+}
+
+fn decode_digit(byte: u8) -> Option<u32> {
+  match byte {
+    b'0'..=b'9' => Some(u32::from(byte - b'0') + 26),
+    b'A'..=b'Z' => Some(u32::from(byte - b'A')),
+    b'a'..=b'z' => Some(u32::from(byte - b'a')),
+    _ => None,
+  }
+}
+
+/// Decodes a single Punycode-encoded label (without the `xn--` prefix)
+/// back into its original Unicode code points.
+fn decode_punycode(input: &str) -> Result<String, ()> {
+  let input = input.as_bytes();
+
+  let (mut output, mut rest) = match input.iter().rposition(|&byte| byte == b'-') {
+    Some(split) if split > 0 => {
+      let prefix = std::str::from_utf8(&input[..split]).map_err(|_| ())?;
+      (prefix.chars().collect::<Vec<char>>(), &input[split + 1..])
+    }
+    _ => (Vec::new(), input),
+  };
+
+  let mut n = INITIAL_N;
+  let mut i = 0u32;
+  let mut bias = INITIAL_BIAS;
+
+  while !rest.is_empty() {
+    let old_i = i;
+    let mut weight = 1u32;
+    let mut k = BASE;
+
+    loop {
+      if rest.is_empty() {
+        return Err(());
+      }
+
+      let digit = decode_digit(rest[0]).ok_or(())?;
+      rest = &rest[1..];
+
+      i = i.checked_add(digit.checked_mul(weight).ok_or(())?).ok_or(())?;
+
+      let t = if k <= bias {
+        TMIN
+      } else if k >= bias + TMAX {
+        TMAX
+      } else {
+        k - bias
+      };
+
+      if digit < t {
+        break;
+      }
+
+      weight = weight.checked_mul(BASE - t).ok_or(())?;
+      k += BASE;
+    }
+
+    let num_points = output.len() as u32 + 1;
+    bias = adapt(i - old_i, num_points, old_i == 0);
+    n = n.checked_add(i / num_points).ok_or(())?;
+    i %= num_points;
+
+    let ch = char::from_u32(n).ok_or(())?;
+    output.insert(i as usize, ch);
+    i += 1;
+  }
+
+  Ok(output.into_iter().collect())
+}
+
+/// Converts an ASCII-compatible (punycode) domain into its Unicode
+/// presentation form, decoding any `xn--` labels. Labels that aren't
+/// punycode, or that fail to decode, are passed through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::domain_to_unicode;
+/// assert_eq!(domain_to_unicode("xn--mnchen-3ya.de"), "münchen.de");
+/// assert_eq!(domain_to_unicode("example.com"), "example.com");
+/// ```
+pub fn domain_to_unicode(domain: &str) -> String {
+  domain
+    .split('.')
+    .map(|label| match label.strip_prefix("xn--") {
+      Some(rest) => decode_punycode(rest).unwrap_or_else(|_| label.to_string()),
+      None => label.to_string(),
+    })
+    .collect::<Vec<_>>()
+    .join(".")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn decodes_punycode_label() {
+    assert_eq!(domain_to_unicode("xn--mnchen-3ya.de"), "münchen.de");
+  }
+
+  #[test]
+  fn passes_through_non_punycode_labels() {
+    assert_eq!(domain_to_unicode("example.com"), "example.com");
+  }
+}
@@ -0,0 +1,206 @@
+/// A minimal recursive-descent validator for well-formed JSON text.
+///
+/// This only checks syntax, it never materializes a tree. It's strict
+/// enough to reject malformed payloads before they're stored as a body
+/// (trailing garbage, missing commas, bad escapes), which is all callers
+/// that only need "is this well-formed" need.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::is_valid_json;
+/// assert!(is_valid_json(r#"{"name": "PHP", "version": 8}"#));
+/// assert!(!is_valid_json(r#"{"name": "PHP""#));
+/// ```
+pub fn is_valid_json(input: &str) -> bool {
+  let mut chars = input.chars().peekable();
+  skip_whitespace(&mut chars);
+
+  if !parse_value(&mut chars) {
+    return false;
+  }
+
+  skip_whitespace(&mut chars);
+  chars.next().is_none()
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+  while matches!(chars.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+    chars.next();
+  }
+}
+
+fn parse_value(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> bool {
+  skip_whitespace(chars);
+
+  match chars.peek() {
+    Some('"') => parse_string(chars),
+    Some('{') => parse_object(chars),
+    Some('[') => parse_array(chars),
+    Some('t') => parse_literal(chars, "true"),
+    Some('f') => parse_literal(chars, "false"),
+    Some('n') => parse_literal(chars, "null"),
+    Some(c) if c.is_ascii_digit() || *c == '-' => parse_number(chars),
+    _ => false,
+  }
+}
+
+fn parse_literal(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, literal: &str) -> bool {
+  for expected in literal.chars() {
+    if chars.next() != Some(expected) {
+      return false;
+    }
+  }
+  true
+}
+
+fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> bool {
+  if chars.next() != Some('"') {
+    return false;
+  }
+
+  loop {
+    match chars.next() {
+      Some('"') => return true,
+      Some('\\') => match chars.next() {
+        Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => continue,
+        Some('u') => {
+          for _ in 0..4 {
+            if !matches!(chars.next(), Some(c) if c.is_ascii_hexdigit()) {
+              return false;
+            }
+          }
+        }
+        _ => return false,
+      },
+      Some(_) => continue,
+      None => return false,
+    }
+  }
+}
+
+fn parse_number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> bool {
+  let mut saw_digit = false;
+
+  if chars.peek() == Some(&'-') {
+    chars.next();
+  }
+
+  while matches!(chars.peek(), Some(c) if c.is_ascii_digit()) {
+    chars.next();
+    saw_digit = true;
+  }
+
+  if !saw_digit {
+    return false;
+  }
+
+  if chars.peek() == Some(&'.') {
+    chars.next();
+    let mut saw_frac_digit = false;
+    while matches!(chars.peek(), Some(c) if c.is_ascii_digit()) {
+      chars.next();
+      saw_frac_digit = true;
+    }
+    if !saw_frac_digit {
+      return false;
+    }
+  }
+
+  if matches!(chars.peek(), Some('e' | 'E')) {
+    chars.next();
+    if matches!(chars.peek(), Some('+' | '-')) {
+      chars.next();
+    }
+    let mut saw_exp_digit = false;
+    while matches!(chars.peek(), Some(c) if c.is_ascii_digit()) {
+      chars.next();
+      saw_exp_digit = true;
+    }
+    if !saw_exp_digit {
+      return false;
+    }
+  }
+
+  true
+}
+
+fn parse_array(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> bool {
+  if chars.next() != Some('[') {
+    return false;
+  }
+
+  skip_whitespace(chars);
+  if chars.peek() == Some(&']') {
+    chars.next();
+    return true;
+  }
+
+  loop {
+    if !parse_value(chars) {
+      return false;
+    }
+
+    skip_whitespace(chars);
+    match chars.next() {
+      Some(',') => continue,
+      Some(']') => return true,
+      _ => return false,
+    }
+  }
+}
+
+fn parse_object(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> bool {
+  if chars.next() != Some('{') {
+    return false;
+  }
+
+  skip_whitespace(chars);
+  if chars.peek() == Some(&'}') {
+    chars.next();
+    return true;
+  }
+
+  loop {
+    skip_whitespace(chars);
+    if !parse_string(chars) {
+      return false;
+    }
+
+    skip_whitespace(chars);
+    if chars.next() != Some(':') {
+      return false;
+    }
+
+    if !parse_value(chars) {
+      return false;
+    }
+
+    skip_whitespace(chars);
+    match chars.next() {
+      Some(',') => continue,
+      Some('}') => return true,
+      _ => return false,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn accepts_well_formed_json() {
+    assert!(is_valid_json(r#"{"a": [1, 2.5, -3e10, true, false, null, "x\"y"]}"#));
+    assert!(is_valid_json("42"));
+    assert!(is_valid_json(r#""just a string""#));
+  }
+
+  #[test]
+  fn rejects_malformed_json() {
+    assert!(!is_valid_json(r#"{"a": 1"#));
+    assert!(!is_valid_json(r#"{"a": 1,}"#));
+    assert!(!is_valid_json("not json"));
+    assert!(!is_valid_json(r#"{"a": 1} trailing"#));
+  }
+}
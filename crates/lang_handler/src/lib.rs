@@ -183,20 +183,41 @@
 //! assert_eq!(response.body(), "Hello, world!");
 //! ```
 
+mod conditional;
+mod cookie;
+mod cors;
+mod extensions;
 #[cfg(feature = "c")]
 mod ffi;
 mod handler;
 mod headers;
+mod idna;
+mod json;
+mod method;
+pub mod negotiation;
+mod percent;
 mod request;
 mod response;
 pub mod rewrite;
+mod status;
 mod test;
+mod version;
 
+pub use conditional::ConditionalHandler;
+pub use cookie::{parse_cookie_header, parse_set_cookie_header, Cookie, SameSite};
+pub use cors::{CorsHandler, CorsPolicy};
+pub use extensions::Extensions;
 #[cfg(feature = "c")]
 pub use ffi::*;
 pub use handler::Handler;
-pub use headers::{Header, Headers};
+pub use headers::{Header, HeaderEntry, Headers, OccupiedHeaderEntry, VacantHeaderEntry};
+pub use idna::domain_to_unicode;
+pub use json::is_valid_json;
+pub use method::Method;
+pub use percent::{percent_decode, percent_encode, EncodeSet};
 pub use request::{Request, RequestBuilder, RequestBuilderException};
 pub use response::{Response, ResponseBuilder};
-pub use test::{MockRoot, MockRootBuilder};
+pub use status::Status;
+pub use test::{MockRoot, MockRootBuilder, TestRequest};
 pub use url::Url;
+pub use version::Version;
@@ -0,0 +1,109 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// The HTTP method of a [`Request`](crate::Request).
+///
+/// Parsing (via [`FromStr`] or `From<&str>`/`From<String>`) is
+/// case-insensitive, following `http-types`' `Method`, so `"get"`, `"GET"`,
+/// and `"Get"` all produce [`Method::Get`]. Anything that isn't one of the
+/// standard verbs is preserved verbatim as [`Method::Other`] rather than
+/// rejected, since this crate has no opinion on which methods a given
+/// embedder should accept.
+///
+/// # Examples
+///
+/// ```
+/// use lang_handler::Method;
+///
+/// assert_eq!("post".parse(), Ok(Method::Post));
+/// assert_eq!(Method::Get.to_string(), "GET");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Method {
+  /// `GET`
+  Get,
+  /// `HEAD`
+  Head,
+  /// `POST`
+  Post,
+  /// `PUT`
+  Put,
+  /// `DELETE`
+  Delete,
+  /// `CONNECT`
+  Connect,
+  /// `OPTIONS`
+  Options,
+  /// `TRACE`
+  Trace,
+  /// `PATCH`
+  Patch,
+  /// Any method not covered by one of the named variants, preserved as
+  /// given rather than rejected.
+  Other(String),
+}
+
+impl Method {
+  /// Returns the method as an HTTP request-line token, e.g. `"GET"`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Method;
+  ///
+  /// assert_eq!(Method::Patch.as_str(), "PATCH");
+  /// assert_eq!(Method::Other("PURGE".to_string()).as_str(), "PURGE");
+  /// ```
+  pub fn as_str(&self) -> &str {
+    match self {
+      Method::Get => "GET",
+      Method::Head => "HEAD",
+      Method::Post => "POST",
+      Method::Put => "PUT",
+      Method::Delete => "DELETE",
+      Method::Connect => "CONNECT",
+      Method::Options => "OPTIONS",
+      Method::Trace => "TRACE",
+      Method::Patch => "PATCH",
+      Method::Other(method) => method,
+    }
+  }
+}
+
+impl fmt::Display for Method {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl FromStr for Method {
+  type Err = Infallible;
+
+  fn from_str(method: &str) -> Result<Self, Self::Err> {
+    Ok(match method.to_ascii_uppercase().as_str() {
+      "GET" => Method::Get,
+      "HEAD" => Method::Head,
+      "POST" => Method::Post,
+      "PUT" => Method::Put,
+      "DELETE" => Method::Delete,
+      "CONNECT" => Method::Connect,
+      "OPTIONS" => Method::Options,
+      "TRACE" => Method::Trace,
+      "PATCH" => Method::Patch,
+      _ => Method::Other(method.to_string()),
+    })
+  }
+}
+
+impl From<&str> for Method {
+  fn from(method: &str) -> Self {
+    method.parse().unwrap_or_else(|e: Infallible| match e {})
+  }
+}
+
+impl From<String> for Method {
+  fn from(method: String) -> Self {
+    Method::from(method.as_str())
+  }
+}
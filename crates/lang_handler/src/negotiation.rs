@@ -0,0 +1,252 @@
+//! Typed parsing of `Accept`-family headers and `q`-value based content
+//! negotiation, read via [`Headers::parse`](crate::Headers::parse) and
+//! [`Headers::negotiate`](crate::Headers::negotiate).
+
+/// A type that can be parsed from a single raw header value, for use with
+/// [`Headers::parse`](crate::Headers::parse).
+pub trait FromHeader: Sized {
+  /// Parses `value` into `Self`, or returns `None` if it isn't well formed
+  /// enough to interpret.
+  fn from_header(value: &str) -> Option<Self>;
+}
+
+/// A single comma-separated item from a quality-weighted header, such as one
+/// entry of `Accept: text/html;q=0.8, */*;q=0.1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityItem {
+  /// The offer or preference itself, e.g. `"text/html"`.
+  pub value: String,
+
+  /// The relative preference, in `[0, 1]`. Defaults to `1.0` when absent,
+  /// and a malformed `q` parameter is treated as `1.0` rather than
+  /// rejecting the whole item.
+  pub quality: f32,
+}
+
+/// Parses a `q`-value weighted header into its items, sorted by quality
+/// descending. The sort is stable, so items of equal quality keep their
+/// original relative order - this is what lets a client signal preference
+/// by listing a more-preferred value earlier even when quality is tied.
+fn parse_quality_list(header: &str) -> Vec<QualityItem> {
+  let mut items: Vec<QualityItem> = header
+    .split(',')
+    .filter_map(|item| {
+      let mut parts = item.trim().split(';');
+
+      let value = parts.next()?.trim();
+      if value.is_empty() {
+        return None;
+      }
+
+      let mut quality = 1.0;
+      for param in parts {
+        let param = param.trim();
+        if let Some(q) = param.strip_prefix("q=") {
+          quality = q.trim().parse::<f32>().unwrap_or(1.0).clamp(0.0, 1.0);
+        }
+      }
+
+      Some(QualityItem {
+        value: value.to_string(),
+        quality,
+      })
+    })
+    .collect();
+
+  items.sort_by(|a, b| b.quality.partial_cmp(&a.quality).unwrap_or(std::cmp::Ordering::Equal));
+  items
+}
+
+/// Parsed `Accept` header, e.g. `Accept: text/html, application/json;q=0.9`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accept(pub Vec<QualityItem>);
+
+impl FromHeader for Accept {
+  fn from_header(value: &str) -> Option<Self> {
+    Some(Accept(parse_quality_list(value)))
+  }
+}
+
+/// Parsed `Accept-Language` header, e.g. `Accept-Language: en-US, en;q=0.5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptLanguage(pub Vec<QualityItem>);
+
+impl FromHeader for AcceptLanguage {
+  fn from_header(value: &str) -> Option<Self> {
+    Some(AcceptLanguage(parse_quality_list(value)))
+  }
+}
+
+/// Parsed `Accept-Encoding` header, e.g. `Accept-Encoding: gzip, br;q=0.8`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptEncoding(pub Vec<QualityItem>);
+
+impl FromHeader for AcceptEncoding {
+  fn from_header(value: &str) -> Option<Self> {
+    Some(AcceptEncoding(parse_quality_list(value)))
+  }
+}
+
+/// Parsed `Content-Type` header, e.g.
+/// `Content-Type: application/json; charset=utf-8`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentType {
+  /// The media type, e.g. `"application/json"`, lowercased.
+  pub media_type: String,
+
+  /// Parameters following the media type, e.g. `[("charset", "utf-8")]`,
+  /// with parameter names lowercased and quoted values unquoted.
+  pub params: Vec<(String, String)>,
+}
+
+impl FromHeader for ContentType {
+  fn from_header(value: &str) -> Option<Self> {
+    let mut parts = value.split(';');
+
+    let media_type = parts.next()?.trim().to_lowercase();
+    if media_type.is_empty() {
+      return None;
+    }
+
+    let params = parts
+      .filter_map(|param| {
+        let mut kv = param.splitn(2, '=');
+        let name = kv.next()?.trim().to_lowercase();
+        let value = kv.next()?.trim().trim_matches('"').to_string();
+        if name.is_empty() {
+          None
+        } else {
+          Some((name, value))
+        }
+      })
+      .collect();
+
+    Some(ContentType { media_type, params })
+  }
+}
+
+/// A type that can be parsed from a single header value for use with
+/// [`Headers::get_typed`](crate::Headers::get_typed). Unlike [`FromHeader`],
+/// whose `None` means either "absent" or "malformed", `get_typed` tells the
+/// two apart by only calling this when the header is present - a malformed
+/// value surfaces as a descriptive [`HeaderValueError::Invalid`] rather
+/// than being silently treated the same as no header at all.
+pub trait FromHeaderValue: Sized {
+  /// Parses `value`, or returns `None` if it's malformed.
+  fn from_header_value(value: &str) -> Option<Self>;
+}
+
+impl FromHeaderValue for ContentType {
+  fn from_header_value(value: &str) -> Option<Self> {
+    Self::from_header(value)
+  }
+}
+
+impl FromHeaderValue for u64 {
+  fn from_header_value(value: &str) -> Option<Self> {
+    value.trim().parse().ok()
+  }
+}
+
+/// Error returned by [`Headers::get_typed`](crate::Headers::get_typed).
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderValueError {
+  /// The header was not present.
+  Missing,
+
+  /// The header was present but didn't parse as the requested type.
+  Invalid {
+    /// The header name that was requested.
+    name: String,
+    /// The raw value that failed to parse.
+    value: String,
+  },
+}
+
+impl std::fmt::Display for HeaderValueError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      HeaderValueError::Missing => write!(f, "header is missing"),
+      HeaderValueError::Invalid { name, value } => write!(f, "invalid {name} header: {value:?}"),
+    }
+  }
+}
+
+impl std::error::Error for HeaderValueError {}
+
+/// Returns how specifically `pattern` (an `Accept`-family offer, possibly
+/// with a `*` wildcard) matches `candidate` (a concrete, available value),
+/// or `None` if it doesn't match at all. Higher is more specific: an exact
+/// match beats a `type/*` wildcard, which beats a bare `*`/`*/*` wildcard.
+fn match_specificity(candidate: &str, pattern: &str) -> Option<u8> {
+  let candidate = candidate.to_lowercase();
+  let pattern = pattern.to_lowercase();
+
+  if candidate == pattern {
+    return Some(2);
+  }
+
+  if pattern == "*" || pattern == "*/*" {
+    return Some(0);
+  }
+
+  let split = |value: &str| -> Option<(String, String)> {
+    let mut parts = value.splitn(2, '/');
+    let kind = parts.next()?.to_string();
+    let sub = parts.next()?.to_string();
+    Some((kind, sub))
+  };
+
+  let (candidate_type, _) = split(&candidate)?;
+  let (pattern_type, pattern_sub) = split(&pattern)?;
+
+  if pattern_type == candidate_type && pattern_sub == "*" {
+    Some(1)
+  } else {
+    None
+  }
+}
+
+/// Picks the best of `available` according to the `q`-value weighted offers
+/// in `header`, preferring a higher quality and, among ties, a more
+/// specific match (exact over `type/*` over `*`/`*/*`). An offer with
+/// `q=0` is never selected. Returns the first of `available` if `header` is
+/// absent or empty, and `None` if `available` is empty or every candidate
+/// is excluded by an explicit `q=0`.
+pub(crate) fn negotiate<'a>(header: Option<&str>, available: &[&'a str]) -> Option<&'a str> {
+  let offers = header.map(parse_quality_list).unwrap_or_default();
+
+  if offers.is_empty() {
+    return available.first().copied();
+  }
+
+  // Walked explicitly, rather than via Iterator::max_by, so that a tie in
+  // (quality, specificity) keeps the earlier-listed candidate - the same
+  // tie-break `available`'s caller-chosen order implies.
+  let mut best: Option<(&str, f32, u8)> = None;
+
+  for candidate in available {
+    let candidate_best = offers
+      .iter()
+      .filter(|offer| offer.quality > 0.0)
+      .filter_map(|offer| match_specificity(candidate, &offer.value).map(|specificity| (offer.quality, specificity)))
+      .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let Some((quality, specificity)) = candidate_best else {
+      continue;
+    };
+
+    let is_better = match best {
+      None => true,
+      Some((_, best_quality, best_specificity)) => {
+        quality > best_quality || (quality == best_quality && specificity > best_specificity)
+      }
+    };
+
+    if is_better {
+      best = Some((candidate, quality, specificity));
+    }
+  }
+
+  best.map(|(value, _, _)| value)
+}
@@ -0,0 +1,125 @@
+/// The set of characters that stay unescaped when percent-encoding a
+/// string, chosen per RFC 3986 to match where in a URI the string is
+/// destined for. Stricter sets escape more delimiters so the encoded text
+/// can't be mistaken for structure in that part of the URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeSet {
+  /// The strictest set: only unreserved characters (`A-Za-z0-9-._~`) are
+  /// left unescaped. Safe to use as a single opaque path/query/fragment
+  /// component, or a cookie value.
+  Component,
+
+  /// Leaves path sub-delimiters, `:`/`@`, and `/` unescaped, for use as a
+  /// whole path.
+  Path,
+
+  /// Leaves sub-delimiters, `:`/`@`, and `/`/`?` unescaped, for use as a
+  /// whole query string.
+  Query,
+
+  /// Same safe set as `Query`, for use as a whole fragment.
+  Fragment,
+
+  /// Leaves sub-delimiters unescaped, for use inside userinfo
+  /// (`user:password@`), where `:`/`@` must still be escaped.
+  UserInfo,
+}
+
+fn is_safe(byte: u8, set: EncodeSet) -> bool {
+  let unreserved =
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~');
+
+  if unreserved {
+    return true;
+  }
+
+  let sub_delims = matches!(
+    byte,
+    b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'='
+  );
+
+  match set {
+    EncodeSet::Component => false,
+    EncodeSet::UserInfo => sub_delims,
+    EncodeSet::Path => sub_delims || matches!(byte, b':' | b'@' | b'/'),
+    EncodeSet::Query | EncodeSet::Fragment => {
+      sub_delims || matches!(byte, b':' | b'@' | b'/' | b'?')
+    }
+  }
+}
+
+/// Percent-encodes `input`, leaving unescaped whatever characters are safe
+/// for the given `set`.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::{percent_encode, EncodeSet};
+/// assert_eq!(percent_encode("a b/c", EncodeSet::Component), "a%20b%2Fc");
+/// assert_eq!(percent_encode("a b/c", EncodeSet::Path), "a%20b/c");
+/// ```
+pub fn percent_encode(input: &str, set: EncodeSet) -> String {
+  let mut encoded = String::with_capacity(input.len());
+
+  for byte in input.bytes() {
+    if is_safe(byte, set) {
+      encoded.push(byte as char);
+    } else {
+      encoded.push('%');
+      encoded.push_str(&format!("{:02X}", byte));
+    }
+  }
+
+  encoded
+}
+
+/// Percent-decodes `input`, leaving any malformed `%` escape untouched
+/// rather than failing outright.
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::percent_decode;
+/// assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+/// ```
+pub fn percent_decode(input: &str) -> String {
+  let bytes = input.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+
+  while i < bytes.len() {
+    if bytes[i] == b'%' && i + 2 < bytes.len() {
+      let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+      let byte = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok());
+      if let Some(byte) = byte {
+        decoded.push(byte);
+        i += 3;
+        continue;
+      }
+    }
+
+    decoded.push(bytes[i]);
+    i += 1;
+  }
+
+  String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn encodes_per_set() {
+    assert_eq!(percent_encode("a b/c?d", EncodeSet::Component), "a%20b%2Fc%3Fd");
+    assert_eq!(percent_encode("a b/c?d", EncodeSet::Path), "a%20b/c%3Fd");
+    assert_eq!(percent_encode("a b/c?d", EncodeSet::Query), "a%20b/c?d");
+  }
+
+  #[test]
+  fn round_trips_through_decode() {
+    let original = "hello world/?#";
+    let encoded = percent_encode(original, EncodeSet::Component);
+    assert_eq!(percent_decode(&encoded), original);
+  }
+}
@@ -1,4 +1,5 @@
 use std::{
+  collections::HashMap,
   fmt::Debug,
   net::{AddrParseError, SocketAddr},
 };
@@ -6,7 +7,7 @@ use std::{
 use bytes::{Bytes, BytesMut};
 use url::{ParseError, Url};
 
-use crate::Headers;
+use crate::{Extensions, Headers, Method, Version};
 
 /// Represents an HTTP request. Includes the method, URL, headers, and body.
 ///
@@ -36,13 +37,16 @@ use crate::Headers;
 /// ```
 #[derive(Clone, Debug)]
 pub struct Request {
-  method: String,
+  method: Method,
   url: Url,
   headers: Headers,
   // TODO: Support Stream bodies when napi.rs supports it
   body: Bytes,
   local_socket: Option<SocketAddr>,
   remote_socket: Option<SocketAddr>,
+  attributes: HashMap<String, String>,
+  version: Option<Version>,
+  extensions: Extensions,
 }
 
 unsafe impl Sync for Request {}
@@ -76,12 +80,15 @@ impl Request {
     remote_socket: Option<SocketAddr>,
   ) -> Self {
     Self {
-      method,
+      method: method.into(),
       url,
       headers,
       body: body.into(),
       local_socket,
       remote_socket,
+      attributes: HashMap::new(),
+      version: None,
+      extensions: Extensions::new(),
     }
   }
 
@@ -161,6 +168,28 @@ impl Request {
   /// assert_eq!(request.method(), "POST");
   /// ```
   pub fn method(&self) -> &str {
+    self.method.as_str()
+  }
+
+  /// Returns the strongly-typed method of the request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Method, Request, Headers};
+  ///
+  /// let request = Request::new(
+  ///   "post".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// assert_eq!(request.method_enum(), &Method::Post);
+  /// ```
+  pub fn method_enum(&self) -> &Method {
     &self.method
   }
 
@@ -276,6 +305,129 @@ impl Request {
   pub fn remote_socket(&self) -> Option<SocketAddr> {
     self.remote_socket
   }
+
+  /// Returns the HTTP protocol version of the request, if known.
+  ///
+  /// This is `None` when the request was built without an explicit
+  /// [`Version`](crate::Version), e.g. constructed directly by an embedder
+  /// that has no protocol information to report.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Request, Headers};
+  ///
+  /// let request = Request::new(
+  ///   "POST".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// assert_eq!(request.version(), None);
+  /// ```
+  pub fn version(&self) -> Option<Version> {
+    self.version
+  }
+
+  /// Returns the attributes attached to the request.
+  ///
+  /// Attributes are arbitrary string-keyed values that are not part of the
+  /// request itself (method, URL, headers, body), but are produced as a
+  /// side effect of processing it — for example, named segment captures
+  /// recorded by a [`crate::rewrite::Rewriter`] matching a route pattern.
+  /// They let later stages of a rewrite chain, and the eventual request
+  /// handler, recover those values by name.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Request, Headers};
+  ///
+  /// let request = Request::new(
+  ///   "POST".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// assert_eq!(request.attribute("id"), None);
+  /// ```
+  pub fn attribute(&self, key: &str) -> Option<&str> {
+    self.attributes.get(key).map(String::as_str)
+  }
+
+  /// Returns all attributes attached to the request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Request, Headers};
+  ///
+  /// let request = Request::new(
+  ///   "POST".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// assert!(request.attributes().is_empty());
+  /// ```
+  pub fn attributes(&self) -> &HashMap<String, String> {
+    &self.attributes
+  }
+
+  /// Returns the type-keyed [`Extensions`] map attached to the request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Request, Headers};
+  ///
+  /// let request = Request::new(
+  ///   "POST".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// assert_eq!(request.extensions().get::<u32>(), None);
+  /// ```
+  pub fn extensions(&self) -> &Extensions {
+    &self.extensions
+  }
+
+  /// Returns a mutable reference to the type-keyed [`Extensions`] map
+  /// attached to the request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Request, Headers};
+  ///
+  /// let mut request = Request::new(
+  ///   "POST".to_string(),
+  ///   "http://example.com/test.php".parse().unwrap(),
+  ///   Headers::new(),
+  ///   "Hello, World!",
+  ///   None,
+  ///   None,
+  /// );
+  ///
+  /// request.extensions_mut().insert(42u32);
+  /// assert_eq!(request.extensions().get::<u32>(), Some(&42));
+  /// ```
+  pub fn extensions_mut(&mut self) -> &mut Extensions {
+    &mut self.extensions
+  }
 }
 
 /// Errors which may be produced when building a Request from a RequestBuilder.
@@ -283,12 +435,19 @@ impl Request {
 pub enum RequestBuilderException {
   /// Url is required
   MissingUrl,
+
+  /// A [`RewriteChain`](crate::rewrite::RewriteChain) exceeded its configured
+  /// maximum number of passes over its rule set without settling.
+  TooManyRewrites(usize),
 }
 
 impl std::fmt::Display for RequestBuilderException {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     match self {
       RequestBuilderException::MissingUrl => write!(f, "Expected url to be set"),
+      RequestBuilderException::TooManyRewrites(max) => {
+        write!(f, "Exceeded maximum of {max} rewrite chain passes")
+      }
     }
   }
 }
@@ -317,12 +476,15 @@ impl std::fmt::Display for RequestBuilderException {
 /// ```
 #[derive(Clone)]
 pub struct RequestBuilder {
-  method: Option<String>,
+  method: Option<Method>,
   url: Option<Url>,
   headers: Headers,
   body: BytesMut,
   local_socket: Option<SocketAddr>,
   remote_socket: Option<SocketAddr>,
+  attributes: HashMap<String, String>,
+  version: Option<Version>,
+  extensions: Extensions,
 }
 
 impl RequestBuilder {
@@ -343,6 +505,9 @@ impl RequestBuilder {
       body: BytesMut::with_capacity(1024),
       local_socket: None,
       remote_socket: None,
+      attributes: HashMap::new(),
+      version: None,
+      extensions: Extensions::new(),
     }
   }
 
@@ -374,18 +539,27 @@ impl RequestBuilder {
   /// assert_eq!(extended.headers().get("Accept"), Some("text/html".to_string()));
   /// assert_eq!(extended.body(), "Hello, World!");
   /// ```
+  ///
+  /// `extensions` is carried forward too, so a [`Rewriter`](crate::rewrite::Rewriter)
+  /// that stashes routing metadata in them via `extensions_mut()` has that
+  /// state still readable by whatever later stage calls `request.extensions()`,
+  /// rather than it being dropped on each rewrite.
   pub fn extend(request: &Request) -> Self {
     Self {
-      method: Some(request.method().into()),
+      method: Some(request.method_enum().clone()),
       url: Some(request.url().clone()),
       headers: request.headers().clone(),
       body: BytesMut::from(request.body()),
       local_socket: request.local_socket,
       remote_socket: request.remote_socket,
+      attributes: request.attributes.clone(),
+      version: request.version,
+      extensions: request.extensions.clone(),
     }
   }
 
-  /// Sets the method of the request.
+  /// Sets the method of the request. Parsing is case-insensitive - see
+  /// [`Method`].
   ///
   /// # Examples
   ///
@@ -393,14 +567,14 @@ impl RequestBuilder {
   /// use lang_handler::RequestBuilder;
   ///
   /// let request = RequestBuilder::new()
-  ///  .method("POST")
+  ///  .method("post")
   ///  .url("http://example.com/test.php").expect("invalid url")
   ///  .build()
   ///   .expect("should build request");
   ///
   /// assert_eq!(request.method(), "POST");
   /// ```
-  pub fn method<T: Into<String>>(mut self, method: T) -> Self {
+  pub fn method<T: Into<Method>>(mut self, method: T) -> Self {
     self.method = Some(method.into());
     self
   }
@@ -432,6 +606,22 @@ impl RequestBuilder {
     }
   }
 
+  /// Returns the headers set on the builder so far.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::RequestBuilder;
+  ///
+  /// let builder = RequestBuilder::new()
+  ///   .header("Accept", "text/html");
+  ///
+  /// assert_eq!(builder.headers().get("Accept"), Some("text/html".to_string()));
+  /// ```
+  pub fn headers(&self) -> &Headers {
+    &self.headers
+  }
+
   /// Sets a header of the request.
   ///
   /// # Examples
@@ -456,7 +646,8 @@ impl RequestBuilder {
     self
   }
 
-  /// Sets the body of the request.
+  /// Sets the body of the request, and sets the `Content-Length` header to
+  /// match its byte length.
   ///
   /// # Examples
   ///
@@ -470,9 +661,11 @@ impl RequestBuilder {
   ///   .expect("should build request");
   ///
   /// assert_eq!(request.body(), "Hello, World!");
+  /// assert_eq!(request.headers().get("Content-Length"), Some("13".to_string()));
   /// ```
   pub fn body<T: Into<BytesMut>>(mut self, body: T) -> Self {
     self.body = body.into();
+    self.headers.set("Content-Length", self.body.len().to_string());
     self
   }
 
@@ -540,6 +733,70 @@ impl RequestBuilder {
     }
   }
 
+  /// Sets the HTTP protocol version of the request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{RequestBuilder, Version};
+  ///
+  /// let request = RequestBuilder::new()
+  ///   .url("http://example.com/test.php").expect("invalid url")
+  ///   .version(Version::Http2_0)
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert_eq!(request.version(), Some(Version::Http2_0));
+  /// ```
+  pub fn version(mut self, version: Version) -> Self {
+    self.version = Some(version);
+    self
+  }
+
+  /// Returns the attributes set on the builder so far.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::RequestBuilder;
+  ///
+  /// let builder = RequestBuilder::new()
+  ///   .attribute("id", "42");
+  ///
+  /// assert_eq!(builder.attributes().get("id").map(String::as_str), Some("42"));
+  /// ```
+  pub fn attributes(&self) -> &std::collections::HashMap<String, String> {
+    &self.attributes
+  }
+
+  /// Sets an attribute of the request.
+  ///
+  /// Attributes are arbitrary string-keyed values carried alongside the
+  /// request, e.g. named segment captures recorded by a route-matching
+  /// [`crate::rewrite::Rewriter`] so later stages can read them back.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::RequestBuilder;
+  ///
+  /// let request = RequestBuilder::new()
+  ///   .url("http://example.com/test.php").expect("invalid url")
+  ///   .attribute("id", "42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert_eq!(request.attribute("id"), Some("42"));
+  /// ```
+  pub fn attribute<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: Into<String>,
+    V: Into<String>,
+  {
+    self.attributes.insert(key.into(), value.into());
+    self
+  }
+
   /// Builds the request.
   ///
   /// # Examples
@@ -558,12 +815,15 @@ impl RequestBuilder {
   /// ```
   pub fn build(self) -> Result<Request, RequestBuilderException> {
     Ok(Request {
-      method: self.method.unwrap_or_else(|| "GET".to_string()),
+      method: self.method.unwrap_or(Method::Get),
       url: self.url.ok_or(RequestBuilderException::MissingUrl)?,
       headers: self.headers,
       body: self.body.freeze(),
       local_socket: self.local_socket,
       remote_socket: self.remote_socket,
+      attributes: self.attributes,
+      version: self.version,
+      extensions: self.extensions,
     })
   }
 }
@@ -1,6 +1,6 @@
 use bytes::{Bytes, BytesMut};
 
-use super::Headers;
+use super::{parse_set_cookie_header, Cookie, Headers, Status};
 
 /// Represents an HTTP response. This includes the status code, headers, body, log, and exception.
 ///
@@ -21,7 +21,7 @@ use super::Headers;
 /// ```
 #[derive(Clone, Debug)]
 pub struct Response {
-  status: i32,
+  status: Status,
   headers: Headers,
   // TODO: Support Stream bodies when napi.rs supports it
   body: Bytes,
@@ -60,7 +60,7 @@ impl Response {
     L: Into<Bytes>,
   {
     Self {
-      status,
+      status: Status::new(status),
       headers,
       body: body.into(),
       log: log.into(),
@@ -128,7 +128,26 @@ impl Response {
   /// assert_eq!(response.status(), 200);
   /// ```
   pub fn status(&self) -> i32 {
-    self.status
+    self.status.code()
+  }
+
+  /// Returns the standard reason phrase for the response's status code
+  /// (e.g. `"Not Found"` for `404`), or `""` if the code isn't a recognized
+  /// standard one.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::Response;
+  ///
+  /// let response = Response::builder()
+  ///   .status(404)
+  ///   .build();
+  ///
+  /// assert_eq!(response.reason(), "Not Found");
+  /// ```
+  pub fn reason(&self) -> &'static str {
+    self.status.reason()
   }
 
   /// Returns the headers of the response.
@@ -149,6 +168,36 @@ impl Response {
     &self.headers
   }
 
+  /// Returns the name/value pairs of cookies set on the response via
+  /// `Set-Cookie` headers, in the order they were added. Attributes such as
+  /// `Path` or `Max-Age` are discarded - use [`Response::headers`] and
+  /// [`Headers::get_set_cookies`] directly if those are needed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::{Cookie, Response};
+  ///
+  /// let response = Response::builder()
+  ///   .status(200)
+  ///   .cookie(Cookie::new("session", "abc123"))
+  ///   .cookie(Cookie::new("theme", "dark"))
+  ///   .build();
+  ///
+  /// assert_eq!(response.cookies(), vec![
+  ///   ("session".to_string(), "abc123".to_string()),
+  ///   ("theme".to_string(), "dark".to_string()),
+  /// ]);
+  /// ```
+  pub fn cookies(&self) -> Vec<(String, String)> {
+    self
+      .headers
+      .get_set_cookies()
+      .iter()
+      .filter_map(|header| parse_set_cookie_header(header))
+      .collect()
+  }
+
   /// Returns the body of the response.
   ///
   /// # Example
@@ -167,6 +216,38 @@ impl Response {
     self.body.clone()
   }
 
+  /// Splits the body into chunks of at most `chunk_size` bytes, for an
+  /// embedder that wants to emit a large response body to its client
+  /// incrementally rather than writing it in one call.
+  ///
+  /// This slices the already-buffered body rather than avoiding buffering
+  /// it in the first place - the body is still fully materialized by the
+  /// time a `Response` exists - but each chunk is a cheap reference-counted
+  /// [`Bytes::slice`], not a copy.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::Response;
+  ///
+  /// let response = Response::builder()
+  ///   .status(200)
+  ///   .body("Hello, World!")
+  ///   .build();
+  ///
+  /// let chunks: Vec<_> = response.body_chunks(5).collect();
+  /// assert_eq!(chunks, vec!["Hello", ", Wor", "ld!"]);
+  /// ```
+  pub fn body_chunks(&self, chunk_size: usize) -> impl Iterator<Item = Bytes> + '_ {
+    let len = self.body.len();
+    let chunk_size = chunk_size.max(1);
+
+    (0..len).step_by(chunk_size).map(move |start| {
+      let end = (start + chunk_size).min(len);
+      self.body.slice(start..end)
+    })
+  }
+
   /// Returns the log of the response.
   ///
   /// # Example
@@ -223,7 +304,7 @@ impl Response {
 /// ```
 #[derive(Clone, Debug)]
 pub struct ResponseBuilder {
-  status: Option<i32>,
+  status: Option<Status>,
   headers: Headers,
   pub(crate) body: BytesMut,
   pub(crate) log: BytesMut,
@@ -281,24 +362,49 @@ impl ResponseBuilder {
     }
   }
 
-  /// Sets the status code of the response.
+  /// Sets the status code of the response. Accepts a raw `i32` or a
+  /// [`Status`], so callers can write `.status(404)` or
+  /// `.status(Status::NOT_FOUND)` interchangeably.
   ///
   /// # Example
   ///
   /// ```
-  /// use lang_handler::ResponseBuilder;
+  /// use lang_handler::{ResponseBuilder, Status};
   ///
   /// let response = ResponseBuilder::new()
   ///   .status(300)
   ///   .build();
   ///
   /// assert_eq!(response.status(), 300);
+  ///
+  /// let response = ResponseBuilder::new()
+  ///   .status(Status::NOT_FOUND)
+  ///   .build();
+  ///
+  /// assert_eq!(response.status(), 404);
+  /// assert_eq!(response.reason(), "Not Found");
   /// ```
-  pub fn status(&mut self, status: i32) -> &mut Self {
-    self.status = Some(status);
+  pub fn status<S: Into<Status>>(&mut self, status: S) -> &mut Self {
+    self.status = Some(status.into());
     self
   }
 
+  /// Returns the headers set on the builder so far.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::ResponseBuilder;
+  ///
+  /// let mut builder = ResponseBuilder::new();
+  /// builder.header("Content-Type", "text/plain");
+  ///
+  /// assert_eq!(builder.headers().get("Content-Type"), Some("text/plain".to_string()));
+  /// ```
+  pub fn headers(&self) -> &Headers {
+    &self.headers
+  }
+
   /// Sets the headers of the response.
   ///
   /// # Example
@@ -321,6 +427,51 @@ impl ResponseBuilder {
     self
   }
 
+  /// Appends a `Set-Cookie` header for the given cookie. Calling this
+  /// multiple times adds another `Set-Cookie` value each time, keeping the
+  /// earlier ones.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::{Cookie, ResponseBuilder};
+  ///
+  /// let response = ResponseBuilder::new()
+  ///   .cookie(Cookie::new("session", "abc123"))
+  ///   .build();
+  ///
+  /// assert_eq!(response.headers().get("Set-Cookie"), Some("session=abc123".to_string()));
+  /// ```
+  pub fn cookie(&mut self, cookie: Cookie) -> &mut Self {
+    self.headers.add("Set-Cookie", cookie.to_string());
+    self
+  }
+
+  /// Appends a `Set-Cookie` header that expires the named cookie immediately,
+  /// instructing the client to delete it.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use lang_handler::ResponseBuilder;
+  ///
+  /// let response = ResponseBuilder::new()
+  ///   .remove_cookie("session")
+  ///   .build();
+  ///
+  /// assert_eq!(
+  ///   response.headers().get("Set-Cookie"),
+  ///   Some("session=; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT".to_string())
+  /// );
+  /// ```
+  pub fn remove_cookie<N: Into<String>>(&mut self, name: N) -> &mut Self {
+    let cookie = Cookie::new(name, "")
+      .max_age(0)
+      .expires("Thu, 01 Jan 1970 00:00:00 GMT");
+
+    self.cookie(cookie)
+  }
+
   /// Sets the body of the response.
   ///
   /// # Example
@@ -403,7 +554,7 @@ impl ResponseBuilder {
   /// ```
   pub fn build(&self) -> Response {
     Response {
-      status: self.status.unwrap_or(200),
+      status: self.status.unwrap_or_default(),
       headers: self.headers.clone(),
       body: self.body.clone().freeze(),
       log: self.log.clone().freeze(),
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use regex::{Captures as RegexCaptures, Regex};
+
+/// The capture groups produced by a matching [`Condition`](super::Condition),
+/// analogous to Apache `mod_rewrite`'s `%1`..`%9` `RewriteCond` backreferences.
+///
+/// Numbered groups are 1-indexed, matching `regex`'s and `mod_rewrite`'s own
+/// convention - group 0 (the whole match) is never stored. Named groups, such
+/// as those [`RouteCondition`](super::RouteCondition) produces from a
+/// `{name}` path pattern, are available via [`Captures::name`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Captures {
+  groups: Vec<Option<String>>,
+  named: HashMap<String, String>,
+}
+
+impl Captures {
+  /// A Captures with no groups, for Conditions that match without capturing
+  /// anything, such as [`ExistenceCondition`](super::ExistenceCondition).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::Captures;
+  /// let captures = Captures::empty();
+  /// assert_eq!(captures.get(1), None);
+  /// ```
+  pub fn empty() -> Self {
+    Self::default()
+  }
+
+  /// Builds a Captures from a matched [`regex::Regex`] and its
+  /// [`regex::Captures`], carrying forward both numbered and named groups.
+  pub fn from_regex(pattern: &Regex, captures: RegexCaptures) -> Self {
+    let groups = captures
+      .iter()
+      .skip(1)
+      .map(|group| group.map(|group| group.as_str().to_string()))
+      .collect();
+
+    let named = pattern
+      .capture_names()
+      .flatten()
+      .filter_map(|name| captures.name(name).map(|value| (name.to_string(), value.as_str().to_string())))
+      .collect();
+
+    Self { groups, named }
+  }
+
+  /// Builds a Captures carrying only named groups, such as those produced by
+  /// [`PathPattern::captures`](super::super::PathPattern::captures).
+  pub fn from_named(named: HashMap<String, String>) -> Self {
+    Self { groups: vec![], named }
+  }
+
+  /// Returns the numbered group at `index` (1-indexed), or `None` if there's
+  /// no such group or it didn't participate in the match.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, PathCondition};
+  /// # let docroot = std::env::temp_dir();
+  /// # use lang_handler::Request;
+  /// let condition = PathCondition::new("^/user/([0-9]+)$")
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/user/42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// let captures = condition.matches(&request, &docroot).expect("should match");
+  /// assert_eq!(captures.get(1), Some("42"));
+  /// assert_eq!(captures.get(2), None);
+  /// ```
+  pub fn get(&self, index: usize) -> Option<&str> {
+    if index == 0 {
+      return None;
+    }
+
+    self.groups.get(index - 1)?.as_deref()
+  }
+
+  /// Returns the named group `name`, or `None` if there's no such group or it
+  /// didn't participate in the match.
+  pub fn name(&self, name: &str) -> Option<&str> {
+    self.named.get(name).map(String::as_str)
+  }
+}
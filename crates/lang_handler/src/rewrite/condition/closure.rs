@@ -1,21 +1,22 @@
 use std::path::Path;
 
-use super::{Condition, Request};
+use super::{Captures, Condition, Request};
 
 impl<F> Condition for F
 where
-  F: Fn(&Request, &Path) -> bool + Sync + Send,
+  F: Fn(&Request, &Path) -> Option<Captures> + Sync + Send,
 {
-  /// Matches if calling the Fn(&Request) with the given request returns true
+  /// Matches if calling the Fn(&Request, &Path) with the given request
+  /// returns `Some`, carrying forward whatever captures it returns.
   ///
   /// # Examples
   ///
   /// ```
   /// # use std::path::Path;
-  /// # use lang_handler::{Request, rewrite::Condition};
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition}};
   /// # let docroot = std::env::temp_dir();
-  /// let condition = |request: &Request, _docroot: &Path| -> bool {
-  ///   request.url().path().contains("/foo")
+  /// let condition = |request: &Request, _docroot: &Path| -> Option<Captures> {
+  ///   request.url().path().contains("/foo").then(Captures::empty)
   /// };
   ///
   /// let request = Request::builder()
@@ -23,9 +24,9 @@ where
   ///   .build()
   ///   .expect("request should build");
   ///
-  /// assert!(!condition.matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_none());
   /// ```
-  fn matches(&self, request: &Request, docroot: &Path) -> bool {
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
     self(request, docroot)
   }
 }
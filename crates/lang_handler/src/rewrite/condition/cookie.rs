@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use regex::{Error, Regex};
+
+use super::{Captures, Condition};
+use crate::{cookie::parse_cookie_header, Request};
+
+/// Matches a named cookie's value, parsed from the request's `Cookie`
+/// header, to a regex pattern.
+#[derive(Clone, Debug)]
+pub struct CookieCondition {
+  name: String,
+  pattern: Regex,
+}
+
+impl CookieCondition {
+  /// Construct a new CookieCondition matching the named cookie's value
+  /// against the given Regex pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::CookieCondition;
+  /// let condition = CookieCondition::new("session", ".+")
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn new<N, R>(name: N, pattern: R) -> Result<Box<Self>, Error>
+  where
+    N: Into<String>,
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+  {
+    let name = name.into();
+    let pattern = pattern.try_into()?;
+    Ok(Box::new(Self { name, pattern }))
+  }
+}
+
+impl Condition for CookieCondition {
+  /// A CookieCondition matches a request if its `Cookie` header carries the
+  /// named cookie and its value matches the given Regex pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, CookieCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = CookieCondition::new("session", "^abc")
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php")
+  ///   .header("Cookie", "session=abc123; theme=dark")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # let request = Request::builder()
+  /// #   .url("http://example.com/index.php")
+  /// #   .header("Cookie", "theme=dark")
+  /// #   .build()
+  /// #   .expect("should build request");
+  /// #
+  /// # assert!(condition.matches(&request, &docroot).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    let value = request
+      .headers()
+      .get_line("Cookie")
+      .map(|line| parse_cookie_header(&line))
+      .unwrap_or_default()
+      .into_iter()
+      .find(|(name, _)| name == &self.name)
+      .map(|(_, value)| value)?;
+
+    self
+      .pattern
+      .captures(&value)
+      .map(|captures| Captures::from_regex(&self.pattern, captures))
+  }
+}
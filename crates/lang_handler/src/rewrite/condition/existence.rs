@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use super::Captures;
 use super::Condition;
 use super::Request;
 
@@ -31,21 +32,22 @@ impl Condition for ExistenceCondition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!condition.matches(
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
   /// #   &request.extend()
   /// #      .url("http://example.com/does_not_exist.php")
   /// #      .build()
   /// #      .expect("should build request"),
   /// #   &docroot
-  /// # ));
+  /// # ).is_none());
   /// ```
-  fn matches(&self, request: &Request, docroot: &Path) -> bool {
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
     let path = request.url().path();
     docroot
       .join(path.strip_prefix("/").unwrap_or(path))
       .canonicalize()
-      .is_ok()
+      .ok()
+      .map(|_| Captures::empty())
   }
 }
 
@@ -77,20 +79,21 @@ impl Condition for NonExistenceCondition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!condition.matches(
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
   /// #   &request.extend()
   /// #      .url("http://example.com/exists.php")
   /// #      .build()
   /// #      .expect("should build request"),
   /// #   &docroot
-  /// # ));
+  /// # ).is_none());
   /// ```
-  fn matches(&self, request: &Request, docroot: &Path) -> bool {
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
     let path = request.url().path();
     docroot
       .join(path.strip_prefix("/").unwrap_or(path))
       .canonicalize()
-      .is_err()
+      .err()
+      .map(|_| Captures::empty())
   }
 }
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use super::Captures;
+use super::Condition;
+use super::Request;
+use crate::rewrite::rewriter::try_files::{expand_candidate, resolve_candidate};
+
+/// Match if an expanded filesystem path template resolves to a real file
+/// under the docroot.
+///
+/// Unlike [`ExistenceCondition`](super::ExistenceCondition), which only ever
+/// checks the request path itself, `ExistsCondition` checks an arbitrary
+/// template that may reference `$uri` (the request path), the same
+/// placeholder [`TryFilesRewriter`](crate::rewrite::TryFilesRewriter) uses
+/// for its candidate list. [`Condition::matches`] receives no captures, so
+/// `%1`..`%9` placeholders in the template expand to an empty string rather
+/// than to another condition's captures. This lets a rule guard on a derived path, e.g.
+/// `$uri.php`, rather than only the literal request path.
+#[derive(Clone, Debug)]
+pub struct ExistsCondition(String);
+
+impl ExistsCondition {
+  /// Construct an ExistsCondition checking the given path template.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::ExistsCondition;
+  /// let condition = ExistsCondition::new("$uri.php");
+  /// ```
+  pub fn new<S: Into<String>>(template: S) -> Box<Self> {
+    Box::new(Self(template.into()))
+  }
+}
+
+impl Condition for ExistsCondition {
+  /// An ExistsCondition matches a request if its expanded template resolves
+  /// to an existing file under `docroot`, the same way
+  /// [`TryFilesRewriter`](crate::rewrite::TryFilesRewriter) resolves its
+  /// candidates.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::{
+  /// #   rewrite::{Condition, ExistsCondition},
+  /// #   Request,
+  /// #   MockRoot
+  /// # };
+  /// #
+  /// # let docroot = MockRoot::builder()
+  /// #   .file("exists.php", "<?php echo \"Hello, world!\"; ?>")
+  /// #   .build()
+  /// #   .expect("should prepare docroot");
+  /// let condition = ExistsCondition::new("$uri");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/exists.php")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &request.extend()
+  /// #      .url("http://example.com/does_not_exist.php")
+  /// #      .build()
+  /// #      .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
+    let uri = request.url().path();
+    let expanded = expand_candidate(&self.0, uri, None);
+    resolve_candidate(docroot, &expanded).map(|_| Captures::empty())
+  }
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use super::{Condition, Request};
+use super::{Captures, Condition, Request};
 
 // Tested via Condition::and(...) and Condition::or(...) doctests
 
@@ -18,6 +18,80 @@ where
   And(Box<A>, Box<B>),
 }
 
+/// A [`ConditionGroup`] built with [`ConditionGroup::and`], matching only if
+/// both wrapped conditions match - short-circuiting on the first non-match.
+/// An alias rather than a distinct type, so it composes with [`ConditionExt`](super::ConditionExt)
+/// and [`ConditionGroup`] the same way.
+pub type AndCondition<A, B> = ConditionGroup<A, B>;
+
+/// A [`ConditionGroup`] built with [`ConditionGroup::or`], matching if either
+/// wrapped condition matches - short-circuiting on the first match. An alias
+/// rather than a distinct type, so it composes with [`ConditionExt`](super::ConditionExt)
+/// and [`ConditionGroup`] the same way.
+pub type OrCondition<A, B> = ConditionGroup<A, B>;
+
+/// Negates a single condition, matching whenever the wrapped condition does
+/// not.
+pub struct NotCondition<A>(Box<A>)
+where
+  A: Condition + ?Sized;
+
+impl<A> NotCondition<A>
+where
+  A: Condition + ?Sized,
+{
+  /// Constructs a new NotCondition wrapping the given condition.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use std::path::Path;
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition, NotCondition}};
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = NotCondition::new(Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }));
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// ```
+  pub fn new(condition: Box<A>) -> Box<Self> {
+    Box::new(NotCondition(condition))
+  }
+}
+
+impl<A> Condition for NotCondition<A>
+where
+  A: Condition + ?Sized,
+{
+  /// Evaluates the wrapped condition against the provided request, inverting
+  /// its result.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use std::path::Path;
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition, NotCondition}};
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = NotCondition::new(Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())));
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_none());
+  /// ```
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
+    match self.0.matches(request, docroot) {
+      Some(_) => None,
+      None => Some(Captures::empty()),
+    }
+  }
+}
+
 impl<A, B> ConditionGroup<A, B>
 where
   A: Condition + ?Sized,
@@ -30,11 +104,11 @@ where
   ///
   /// ```
   /// # use std::path::Path;
-  /// # use lang_handler::{Request, rewrite::{Condition, ConditionGroup}};
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition, ConditionGroup}};
   /// # let docroot = std::env::temp_dir();
   /// let condition = ConditionGroup::and(
-  ///   Box::new(|_req: &Request, _docroot: &Path| true),
-  ///   Box::new(|_req: &Request, _docroot: &Path| false),
+  ///   Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())),
+  ///   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
   /// );
   ///
   /// let request = Request::builder()
@@ -42,12 +116,12 @@ where
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(!condition.matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_none());
   /// #
   /// # assert!(ConditionGroup::and(
-  /// #   Box::new(|_req: &Request, _docroot: &Path| true),
-  /// #   Box::new(|_req: &Request, _docroot: &Path| true),
-  /// # ).matches(&request, &docroot));
+  /// #   Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())),
+  /// #   Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())),
+  /// # ).matches(&request, &docroot).is_some());
   /// ```
   pub fn and(a: Box<A>, b: Box<B>) -> Box<Self> {
     Box::new(ConditionGroup::And(a, b))
@@ -60,11 +134,11 @@ where
   ///
   /// ```
   /// # use std::path::Path;
-  /// # use lang_handler::{Request, rewrite::{Condition, ConditionGroup}};
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition, ConditionGroup}};
   /// # let docroot = std::env::temp_dir();
   /// let condition = ConditionGroup::or(
-  ///   Box::new(|_req: &Request, _docroot: &Path| true),
-  ///   Box::new(|_req: &Request, _docroot: &Path| false),
+  ///   Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())),
+  ///   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
   /// );
   ///
   /// let request = Request::builder()
@@ -72,12 +146,12 @@ where
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_some());
   /// #
-  /// # assert!(!ConditionGroup::or(
-  /// #   Box::new(|_req: &Request, _docroot: &Path| false),
-  /// #   Box::new(|_req: &Request, _docroot: &Path| false),
-  /// # ).matches(&request, &docroot));
+  /// # assert!(ConditionGroup::or(
+  /// #   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
+  /// #   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
+  /// # ).matches(&request, &docroot).is_none());
   pub fn or(a: Box<A>, b: Box<B>) -> Box<Self> {
     Box::new(ConditionGroup::Or(a, b))
   }
@@ -95,10 +169,10 @@ where
   /// ```
   /// # use std::path::Path;
   /// # let docroot = std::env::temp_dir();
-  /// # use lang_handler::{Request, rewrite::{Condition, ConditionGroup}};
+  /// # use lang_handler::{Request, rewrite::{Captures, Condition, ConditionGroup}};
   /// let condition = ConditionGroup::or(
-  ///   Box::new(|_req: &Request, _docroot: &Path| true),
-  ///   Box::new(|_req: &Request, _docroot: &Path| false),
+  ///   Box::new(|_req: &Request, _docroot: &Path| Some(Captures::empty())),
+  ///   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
   /// );
   ///
   /// let request = Request::builder()
@@ -106,16 +180,19 @@ where
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!ConditionGroup::or(
-  /// #   Box::new(|_req: &Request, _docroot: &Path| false),
-  /// #   Box::new(|_req: &Request, _docroot: &Path| false),
-  /// # ).matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(ConditionGroup::or(
+  /// #   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
+  /// #   Box::new(|_req: &Request, _docroot: &Path| -> Option<Captures> { None }),
+  /// # ).matches(&request, &docroot).is_none());
   /// ```
-  fn matches(&self, request: &Request, docroot: &Path) -> bool {
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
     match self {
-      ConditionGroup::Or(a, b) => a.matches(request, docroot) || b.matches(request, docroot),
-      ConditionGroup::And(a, b) => a.matches(request, docroot) && b.matches(request, docroot),
+      ConditionGroup::Or(a, b) => a.matches(request, docroot).or_else(|| b.matches(request, docroot)),
+      ConditionGroup::And(a, b) => {
+        a.matches(request, docroot)?;
+        b.matches(request, docroot)
+      }
     }
   }
 }
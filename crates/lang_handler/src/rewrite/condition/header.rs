@@ -2,6 +2,7 @@ use std::{fmt::Debug, path::Path};
 
 use regex::{Error, Regex};
 
+use super::Captures;
 use super::Condition;
 use crate::Request;
 
@@ -55,20 +56,20 @@ impl Condition for HeaderCondition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!condition.matches(
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
   /// #   &request.extend()
   /// #     .header("TEST", "bar")
   /// #     .build()
   /// #     .expect("should build request"),
   /// #   &docroot
-  /// # ));
+  /// # ).is_none());
   /// ```
-  fn matches(&self, request: &Request, _docroot: &Path) -> bool {
-    request
-      .headers()
-      .get_line(&self.name)
-      .map(|line| self.pattern.is_match(&line))
-      .unwrap_or(false)
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    let line = request.headers().get_line(&self.name)?;
+    self
+      .pattern
+      .captures(&line)
+      .map(|captures| Captures::from_regex(&self.pattern, captures))
   }
 }
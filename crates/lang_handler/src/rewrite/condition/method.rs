@@ -2,6 +2,7 @@ use std::{fmt::Debug, path::Path};
 
 use regex::{Error, Regex};
 
+use super::Captures;
 use super::Condition;
 use crate::Request;
 
@@ -50,16 +51,19 @@ impl Condition for MethodCondition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!condition.matches(
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
   /// #   &request.extend()
   /// #     .method("POST")
   /// #     .build()
   /// #     .expect("should build request"),
   /// #   &docroot
-  /// # ));
+  /// # ).is_none());
   /// ```
-  fn matches(&self, request: &Request, _docroot: &Path) -> bool {
-    self.0.is_match(request.method())
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    self
+      .0
+      .captures(request.method())
+      .map(|captures| Captures::from_regex(&self.0, captures))
   }
 }
@@ -1,26 +1,44 @@
+mod captures;
 mod closure;
+mod cookie;
 mod existence;
+mod exists;
 mod group;
 mod header;
 mod method;
 mod path;
+mod query;
+mod route;
+mod set;
 
 use std::path::Path;
 
 use crate::Request;
 
+pub use captures::Captures;
+pub use cookie::CookieCondition;
 pub use existence::{ExistenceCondition, NonExistenceCondition};
-pub use group::ConditionGroup;
+pub use exists::ExistsCondition;
+pub use group::{AndCondition, ConditionGroup, NotCondition, OrCondition};
 pub use header::HeaderCondition;
 pub use method::MethodCondition;
 pub use path::PathCondition;
+pub use query::{
+  QueryCondition, QueryParamCondition, QueryParamExistenceCondition, QueryParamNonExistenceCondition,
+};
+pub use route::RouteCondition;
+pub use set::{ConditionOperation, ConditionSet};
 
 /// A Condition is used to match against request state before deciding to apply
 /// a given Rewrite or set of Rewrites.
 pub trait Condition: Sync + Send {
-  /// A Condition must implement a `matches(request) -> bool` method which
-  /// receives a request object to determine if the condition is met.
-  fn matches(&self, request: &Request, docroot: &Path) -> bool;
+  /// A Condition must implement a `matches(request, docroot) -> Option<Captures>`
+  /// method which receives a request object to determine if the condition is
+  /// met. `None` means the condition did not match; `Some(captures)` means it
+  /// did, carrying forward any capture groups - such as a [`PathCondition`]'s
+  /// regex groups - for a [`Rewriter`](super::Rewriter) to expand as `%1`..`%9`
+  /// backreferences via [`Rewriter::rewrite_with`](super::Rewriter::rewrite_with).
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures>;
 }
 
 impl<T: ?Sized> ConditionExt for T where T: Condition {}
@@ -51,7 +69,7 @@ pub trait ConditionExt: Condition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_some());
   /// #
   /// # // SHould _not_ match if either condition does not match
   /// # let only_header = Request::builder()
@@ -60,14 +78,14 @@ pub trait ConditionExt: Condition {
   /// #   .build()
   /// #   .expect("request should build");
   /// #
-  /// # assert!(!condition.matches(&only_header, &docroot));
+  /// # assert!(condition.matches(&only_header, &docroot).is_none());
   /// #
   /// # let only_url = Request::builder()
   /// #   .url("http://example.com/index.php")
   /// #   .build()
   /// #   .expect("request should build");
   /// #
-  /// # assert!(!condition.matches(&only_url, &docroot));
+  /// # assert!(condition.matches(&only_url, &docroot).is_none());
   /// ```
   fn and<C>(self: Box<Self>, other: Box<C>) -> Box<ConditionGroup<Self, C>>
   where
@@ -99,7 +117,7 @@ pub trait ConditionExt: Condition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
+  /// assert!(condition.matches(&request, &docroot).is_some());
   /// #
   /// # // Should match if one condition does not
   /// # let only_header = Request::builder()
@@ -108,14 +126,14 @@ pub trait ConditionExt: Condition {
   /// #   .build()
   /// #   .expect("request should build");
   /// #
-  /// # assert!(condition.matches(&only_header, &docroot));
+  /// # assert!(condition.matches(&only_header, &docroot).is_some());
   /// #
   /// # let only_url = Request::builder()
   /// #   .url("http://example.com/index.php")
   /// #   .build()
   /// #   .expect("request should build");
   /// #
-  /// # assert!(condition.matches(&only_url, &docroot));
+  /// # assert!(condition.matches(&only_url, &docroot).is_some());
   /// ```
   fn or<C>(self: Box<Self>, other: Box<C>) -> Box<ConditionGroup<Self, C>>
   where
@@ -123,4 +141,37 @@ pub trait ConditionExt: Condition {
   {
     ConditionGroup::or(self, other)
   }
+
+  /// Make a new condition which passes only if this condition does not
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::{
+  /// #  Request,
+  /// #  rewrite::{Condition, ConditionExt, PathCondition}
+  /// # };
+  /// # let docroot = std::env::temp_dir();
+  /// let path = PathCondition::new("^/admin")
+  ///   .expect("should be valid regex");
+  ///
+  /// let condition = path.not();
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// #
+  /// # let admin_request = Request::builder()
+  /// #   .url("http://example.com/admin")
+  /// #   .build()
+  /// #   .expect("should build request");
+  /// #
+  /// # assert!(condition.matches(&admin_request, &docroot).is_none());
+  /// ```
+  fn not(self: Box<Self>) -> Box<NotCondition<Self>> {
+    NotCondition::new(self)
+  }
 }
@@ -2,6 +2,7 @@ use std::{fmt::Debug, path::Path};
 
 use regex::{Error, Regex};
 
+use super::Captures;
 use super::Condition;
 use super::Request;
 
@@ -49,16 +50,19 @@ impl Condition for PathCondition {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// assert!(condition.matches(&request, &docroot));
-  /// # assert!(!condition.matches(
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
   /// #   &request.extend()
   /// #     .url("http://example.com/other.php")
   /// #     .build()
   /// #     .expect("should build request"),
   /// #   &docroot
-  /// # ));
+  /// # ).is_none());
   /// ```
-  fn matches(&self, request: &Request, _docroot: &Path) -> bool {
-    self.pattern.is_match(request.url().path())
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    self
+      .pattern
+      .captures(request.url().path())
+      .map(|captures| Captures::from_regex(&self.pattern, captures))
   }
 }
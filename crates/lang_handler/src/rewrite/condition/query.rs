@@ -0,0 +1,258 @@
+use std::{fmt::Debug, path::Path};
+
+use regex::{Error, Regex};
+
+use super::Captures;
+use super::Condition;
+use super::Request;
+
+/// Match request's raw query string to a regex pattern
+#[derive(Clone, Debug)]
+pub struct QueryCondition {
+  pattern: Regex,
+}
+
+impl QueryCondition {
+  /// Construct a new QueryCondition matching the given Regex pattern against
+  /// the raw query string of a request.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryCondition;
+  /// let condition = QueryCondition::new("^foo=bar$")
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn new<R>(pattern: R) -> Result<Box<Self>, Error>
+  where
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+  {
+    let pattern = pattern.try_into()?;
+    Ok(Box::new(Self { pattern }))
+  }
+}
+
+impl Condition for QueryCondition {
+  /// A QueryCondition matches a request if the raw query string of the
+  /// request url matches the pattern given when constructing the
+  /// QueryCondition. A request with no query string matches against an
+  /// empty string.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, QueryCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = QueryCondition::new("^foo=bar$")
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php?foo=bar")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &request.extend()
+  /// #     .url("http://example.com/index.php?foo=baz")
+  /// #     .build()
+  /// #     .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    self
+      .pattern
+      .captures(request.url().query().unwrap_or(""))
+      .map(|captures| Captures::from_regex(&self.pattern, captures))
+  }
+}
+
+/// Match a named query parameter's value to a regex pattern, e.g. to express
+/// a rule like "only rewrite when `?debug=1` is present" - an absent
+/// parameter never matches.
+#[derive(Clone, Debug)]
+pub struct QueryParamCondition {
+  name: String,
+  pattern: Regex,
+}
+
+impl QueryParamCondition {
+  /// Construct a new QueryParamCondition matching the named query
+  /// parameter's value against the given Regex pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryParamCondition;
+  /// let condition = QueryParamCondition::new("page", "^[0-9]+$")
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn new<N, R>(name: N, pattern: R) -> Result<Box<Self>, Error>
+  where
+    N: Into<String>,
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+  {
+    let name = name.into();
+    let pattern = pattern.try_into()?;
+    Ok(Box::new(Self { name, pattern }))
+  }
+}
+
+impl Condition for QueryParamCondition {
+  /// A QueryParamCondition matches a request if the named query parameter
+  /// is present and its value matches the given Regex pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, QueryParamCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = QueryParamCondition::new("page", "^[0-9]+$")
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php?page=42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &request.extend()
+  /// #     .url("http://example.com/index.php?page=abc")
+  /// #     .build()
+  /// #     .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    let value = request
+      .url()
+      .query_pairs()
+      .find(|(key, _)| key == self.name.as_str())
+      .map(|(_, value)| value.into_owned())?;
+
+    self
+      .pattern
+      .captures(&value)
+      .map(|captures| Captures::from_regex(&self.pattern, captures))
+  }
+}
+
+/// Match if a named query parameter is present, regardless of its value.
+#[derive(Clone, Debug)]
+pub struct QueryParamExistenceCondition {
+  name: String,
+}
+
+impl QueryParamExistenceCondition {
+  /// Construct a new QueryParamExistenceCondition matching requests whose
+  /// query string includes the named parameter.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryParamExistenceCondition;
+  /// let condition = QueryParamExistenceCondition::new("page");
+  /// ```
+  pub fn new<N>(name: N) -> Box<Self>
+  where
+    N: Into<String>,
+  {
+    Box::new(Self { name: name.into() })
+  }
+}
+
+impl Condition for QueryParamExistenceCondition {
+  /// A QueryParamExistenceCondition matches a request if the named query
+  /// parameter is present, regardless of its value.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, QueryParamExistenceCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = QueryParamExistenceCondition::new("page");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php?page=1")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &request.extend()
+  /// #     .url("http://example.com/index.php")
+  /// #     .build()
+  /// #     .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    request
+      .url()
+      .query_pairs()
+      .any(|(key, _)| key == self.name.as_str())
+      .then(Captures::empty)
+  }
+}
+
+/// Match if a named query parameter is absent.
+#[derive(Clone, Debug)]
+pub struct QueryParamNonExistenceCondition {
+  name: String,
+}
+
+impl QueryParamNonExistenceCondition {
+  /// Construct a new QueryParamNonExistenceCondition matching requests
+  /// whose query string does not include the named parameter.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryParamNonExistenceCondition;
+  /// let condition = QueryParamNonExistenceCondition::new("page");
+  /// ```
+  pub fn new<N>(name: N) -> Box<Self>
+  where
+    N: Into<String>,
+  {
+    Box::new(Self { name: name.into() })
+  }
+}
+
+impl Condition for QueryParamNonExistenceCondition {
+  /// A QueryParamNonExistenceCondition matches a request if the named query
+  /// parameter is absent.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, QueryParamNonExistenceCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = QueryParamNonExistenceCondition::new("page");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &request.extend()
+  /// #     .url("http://example.com/index.php?page=1")
+  /// #     .build()
+  /// #     .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    (!request.url().query_pairs().any(|(key, _)| key == self.name.as_str())).then(Captures::empty)
+  }
+}
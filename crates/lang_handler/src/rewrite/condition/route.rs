@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use regex::Error;
+
+use super::{Captures, Condition};
+use crate::{rewrite::PathPattern, Request};
+
+/// Matches a request path against a named segment pattern, such as
+/// `/user/{id}/posts/{slug}`.
+///
+/// Unlike [`PathCondition`](super::PathCondition), which matches the path
+/// against a raw regex, `RouteCondition` compiles an actix/Rocket-style
+/// pattern (see [`PathPattern`]) so route definitions read as a literal path
+/// shape rather than a regular expression.
+#[derive(Clone, Debug)]
+pub struct RouteCondition(PathPattern);
+
+impl RouteCondition {
+  /// Construct a new RouteCondition matching the Request path against the
+  /// given segment pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, RouteCondition};
+  /// let condition = RouteCondition::new("/user/{id}")
+  ///   .expect("should be valid pattern");
+  /// ```
+  pub fn new(pattern: &str) -> Result<Box<Self>, Error> {
+    Ok(Box::new(Self(PathPattern::new(pattern)?)))
+  }
+}
+
+impl Condition for RouteCondition {
+  /// A RouteCondition matches a given request if the Request path matches
+  /// the segment pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, RouteCondition};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = RouteCondition::new("/user/{id}")
+  ///   .expect("should be valid pattern");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/user/42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// assert!(condition.matches(&request, &docroot).is_some());
+  /// # assert!(condition.matches(
+  /// #   &Request::builder()
+  /// #     .url("http://example.com/user/42/posts")
+  /// #     .build()
+  /// #     .expect("should build request"),
+  /// #   &docroot
+  /// # ).is_none());
+  /// ```
+  fn matches(&self, request: &Request, _docroot: &Path) -> Option<Captures> {
+    self.0.captures(request.url().path()).map(Captures::from_named)
+  }
+}
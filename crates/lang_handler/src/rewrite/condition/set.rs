@@ -1,30 +1,48 @@
-use std::fmt::Debug;
-use std::hash::Hash;
+use std::path::Path;
 use std::str::FromStr;
 
-use super::Condition;
+use super::{Captures, Condition};
 use crate::Request;
 
-/// Defines if a set of conditions should match with AND or OR logic
+/// Defines how a [`ConditionSet`]'s conditions combine: with AND logic, OR
+/// logic, or negating the combined (AND) result.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ConditionOperation {
+  /// All conditions in the set must match.
   And,
+
+  /// Any condition in the set must match.
   Or,
+
+  /// None of the conditions in the set may match - the AND-combined result
+  /// of the set is negated, the same as wrapping a single condition in
+  /// [`NotCondition`](super::NotCondition).
+  Not,
 }
 
 impl FromStr for ConditionOperation {
   type Err = String;
 
+  /// Parses a [`ConditionOperation`] from a configuration-style string, e.g.
+  /// as read from a rewrite ruleset file. `"not"` and `"!"` both parse to
+  /// [`ConditionOperation::Not`].
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     match s.to_lowercase().as_str() {
       "and" => Ok(ConditionOperation::And),
       "or" => Ok(ConditionOperation::Or),
+      "not" | "!" => Ok(ConditionOperation::Not),
       _ => Err(format!("Unknown condition operation: {}", s)),
     }
   }
 }
 
-/// A set of conditions which may apply together with AND or OR logic
+/// A set of conditions which may apply together with AND, OR, or NOT logic.
+///
+/// Unlike [`ConditionGroup`](super::ConditionGroup), which combines exactly
+/// two conditions at a time, a `ConditionSet` holds an arbitrary number of
+/// conditions, and its operation can be swapped at runtime - useful when a
+/// rewrite ruleset is built up from a configuration format that names its
+/// combination mode as a string, e.g. `"and"`/`"or"`/`"not"`/`"!"`.
 pub struct ConditionSet {
   operation: ConditionOperation,
   conditions: Vec<Box<dyn Condition>>,
@@ -32,7 +50,7 @@ pub struct ConditionSet {
 
 impl ConditionSet {
   /// Construct a new ConditionSet combining multiple Condition checks into
-  /// one set using either AND or OR logic between them.
+  /// one set using AND, OR, or NOT logic.
   pub fn new(operation: ConditionOperation) -> Self {
     Self {
       operation,
@@ -40,10 +58,12 @@ impl ConditionSet {
     }
   }
 
+  /// Changes the operation used to combine this set's conditions.
   pub fn change_operation(&mut self, operation: ConditionOperation) {
     self.operation = operation;
   }
 
+  /// Adds a condition to the set.
   pub fn add_condition(&mut self, condition: Box<dyn Condition>) {
     self.conditions.push(condition);
   }
@@ -56,20 +76,46 @@ impl Default for ConditionSet {
   }
 }
 
+impl ConditionSet {
+  /// Combines the set's conditions with AND logic: `None` as soon as any
+  /// condition fails to match, otherwise `Some` of the last condition's
+  /// captures. An empty set vacuously matches with no captures.
+  fn matches_and(&self, request: &Request, docroot: &Path) -> Option<Captures> {
+    let mut captures = Captures::empty();
+
+    for condition in &self.conditions {
+      captures = condition.matches(request, docroot)?;
+    }
+
+    Some(captures)
+  }
+}
+
 impl Condition for ConditionSet {
   /// A ConditionSet matches a given request when:
   ///
-  /// - Using AND logic and _all_ conditions in the set match
-  /// - Using OR logic and _any_ conditions in the set match
-  fn matches(&self, request: &Request) -> bool {
-    if self.conditions.len() == 0 {
-      true
-    } else {
-      let mut conds = self.conditions.iter();
-      match self.operation {
-        ConditionOperation::And => conds.all(|c| c.matches(request)),
-        ConditionOperation::Or => conds.any(|c| c.matches(request)),
+  /// - Using AND logic and _all_ conditions in the set match.
+  /// - Using OR logic and _any_ condition in the set matches, carrying
+  ///   forward that condition's captures.
+  /// - Using NOT logic and the AND-combined result of the set does _not_
+  ///   match.
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
+    match self.operation {
+      ConditionOperation::And => self.matches_and(request, docroot),
+      ConditionOperation::Or => {
+        if self.conditions.is_empty() {
+          return Some(Captures::empty());
+        }
+
+        self
+          .conditions
+          .iter()
+          .find_map(|condition| condition.matches(request, docroot))
       }
+      ConditionOperation::Not => match self.matches_and(request, docroot) {
+        Some(_) => None,
+        None => Some(Captures::empty()),
+      },
     }
   }
 }
@@ -80,21 +126,55 @@ mod test {
   use crate::rewrite::{HeaderCondition, PathCondition};
 
   #[test]
-  fn test_condition_set() {
+  fn test_condition_set_and() {
+    let docroot = std::env::temp_dir();
     let mut condition_set = ConditionSet::default();
 
     let path_condition = PathCondition::new("^/index.php$").expect("regex should be valid");
-    condition_set.add_condition(Box::new(path_condition));
+    condition_set.add_condition(path_condition);
 
     let header_condition = HeaderCondition::new("TEST", "^foo$").expect("regex should be valid");
-    condition_set.add_condition(Box::new(header_condition));
+    condition_set.add_condition(header_condition);
 
-    let request = Request::builder()
+    let request = crate::Request::builder()
       .url("http://example.com/index.php")
       .header("TEST", "foo")
       .build()
       .expect("request should build");
 
-    assert!(condition_set.matches(&request));
+    assert!(condition_set.matches(&request, &docroot).is_some());
+  }
+
+  #[test]
+  fn test_condition_set_not() {
+    let docroot = std::env::temp_dir();
+    let mut condition_set = ConditionSet::new(ConditionOperation::Not);
+
+    let header_condition = HeaderCondition::new("X-Internal", "^true$").expect("regex should be valid");
+    condition_set.add_condition(header_condition);
+
+    let request = crate::Request::builder()
+      .url("http://example.com/admin")
+      .build()
+      .expect("request should build");
+
+    assert!(condition_set.matches(&request, &docroot).is_some());
+
+    let internal_request = request
+      .extend()
+      .header("X-Internal", "true")
+      .build()
+      .expect("request should build");
+
+    assert!(condition_set.matches(&internal_request, &docroot).is_none());
+  }
+
+  #[test]
+  fn test_condition_operation_from_str() {
+    assert_eq!("and".parse(), Ok(ConditionOperation::And));
+    assert_eq!("OR".parse(), Ok(ConditionOperation::Or));
+    assert_eq!("not".parse(), Ok(ConditionOperation::Not));
+    assert_eq!("!".parse(), Ok(ConditionOperation::Not));
+    assert!(ConditionOperation::from_str("nope").is_err());
   }
 }
@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use crate::{
-  rewrite::{Condition, Rewriter},
+  rewrite::{Condition, MatchContext, RewriteOutcome, Rewriter},
   Request, RequestBuilderException,
 };
 
@@ -64,7 +64,8 @@ where
   /// #     ConditionalRewriter,
   /// #     PathCondition,
   /// #     PathRewriter,
-  /// #     Rewriter
+  /// #     Rewriter,
+  /// #     RewriteOutcome
   /// #   }
   /// # };
   /// # let docroot = std::env::temp_dir();
@@ -82,26 +83,28 @@ where
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = conditional_rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.url().path(), "/foo/index.php".to_string());
+  /// match conditional_rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/foo/index.php".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// #
   /// # let request = Request::builder()
   /// #   .url("http://example.com/other.php")
   /// #   .build()
   /// #   .expect("should build request");
   /// #
-  /// # let new_request = conditional_rewriter.rewrite(request, &docroot)
-  /// #   .expect("should rewrite request");
-  /// #
-  /// # assert_eq!(new_request.url().path(), "/other.php".to_string());
+  /// # assert!(matches!(
+  /// #   conditional_rewriter.rewrite(request, &docroot).expect("should rewrite request"),
+  /// #   RewriteOutcome::Unmatched
+  /// # ));
   /// ```
-  fn rewrite(&self, request: Request, docroot: &Path) -> Result<Request, RequestBuilderException> {
-    if !self.1.matches(&request, docroot) {
-      return Ok(request);
-    }
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let Some(captures) = self.1.matches(&request, docroot) else {
+      return Ok(RewriteOutcome::Unmatched);
+    };
 
-    self.0.rewrite(request, docroot)
+    self.0.rewrite_with(request, docroot, &MatchContext::new(captures))
   }
 }
@@ -0,0 +1,28 @@
+use super::Captures;
+
+/// Carries the [`Captures`] produced by a matching [`Condition`](super::Condition)
+/// through to the [`Rewriter`](super::Rewriter) it guards, so the rewriter can
+/// expand them as `%1`..`%9` backreferences the same way Apache's
+/// `mod_rewrite` expands `RewriteCond` backreferences in a `RewriteRule`.
+///
+/// The default MatchContext carries no captures, representing a Rewriter
+/// applied with no enclosing Condition - in that case `%` backreferences
+/// expand to empty.
+#[derive(Clone, Debug, Default)]
+pub struct MatchContext {
+  captures: Option<Captures>,
+}
+
+impl MatchContext {
+  /// Constructs a new MatchContext carrying the given Captures.
+  pub fn new(captures: Captures) -> Self {
+    Self {
+      captures: Some(captures),
+    }
+  }
+
+  /// Returns the carried Captures, if any.
+  pub fn captures(&self) -> Option<&Captures> {
+    self.captures.as_ref()
+  }
+}
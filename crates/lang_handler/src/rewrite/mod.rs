@@ -10,20 +10,37 @@
 //! There are several types of [`Condition`] for matching Request state:
 //!
 //! - [`HeaderCondition`] matches if named header matches the given pattern.
+//! - [`CookieCondition`] matches if a named cookie, parsed from the
+//!   `Cookie` header, matches the given pattern.
 //! - [`PathCondition`] matches if Request path matches the given pattern.
+//! - [`RouteCondition`] matches if Request path matches a named segment
+//!   pattern, e.g. `/user/{id}`, compiled via [`PathPattern`].
 //! - [`ExistenceCondition`] matches if Request path resolves to a real file.
 //! - [`NonExistenceCondition`] matches if Request path does not resolve.
-//!
-//! In addition to these core types, any function with a `Fn(&Request) -> bool`
-//! signature may also be used anywhere a [`Condition`] is expected. This
-//! allows any arbitrary logic to be applied to decide a match. Because a
-//! Request may be dispatched to any thread, these functions must be
-//! `Send + Sync`.
+//! - [`ExistsCondition`] matches if an expanded `$uri` path
+//!   template - not just the literal Request path - resolves to a real
+//!   file, the same way [`TryFilesRewriter`] resolves its candidates.
+//! - [`QueryCondition`] matches if the raw query string matches the given
+//!   pattern.
+//! - [`QueryParamCondition`] matches if a named query parameter's value
+//!   matches the given pattern.
+//! - [`QueryParamExistenceCondition`] matches if a named query parameter is
+//!   present.
+//! - [`QueryParamNonExistenceCondition`] matches if a named query parameter
+//!   is absent.
+//!
+//! In addition to these core types, any function with a
+//! `Fn(&Request, &Path) -> Option<Captures>` signature may also be used
+//! anywhere a [`Condition`] is expected. This allows any arbitrary logic to
+//! be applied to decide a match, optionally carrying forward capture groups
+//! of its own. Because a Request may be dispatched to any thread, these
+//! functions must be `Send + Sync`.
 //!
 //! ```
-//! # use lang_handler::{Request, rewrite::Condition};
-//! let condition = |request: &Request| -> bool {
-//!   request.url().path().starts_with("/foo")
+//! # use std::path::Path;
+//! # use lang_handler::{Request, rewrite::{Captures, Condition}};
+//! let condition = |request: &Request, _docroot: &Path| -> Option<Captures> {
+//!   request.url().path().starts_with("/foo").then(Captures::empty)
 //! };
 //! ```
 //!
@@ -31,30 +48,72 @@
 //! conditions using `condition.and(other)` or `condition.or(other)` to apply
 //! conditions with AND or OR logic respectively.
 //!
+//! A matching Condition's [`Captures`] - such as a [`PathCondition`]'s regex
+//! groups, or a [`RouteCondition`]'s named segments - are threaded through to
+//! the guarded [`Rewriter`] so it can expand them as `%1`..`%9` and `%%`
+//! backreferences, the same way Apache's `mod_rewrite` expands a
+//! `RewriteCond`'s backreferences in the `RewriteRule` it guards. See
+//! [`Rewriter::rewrite_with`].
+//!
 //! # Rewriters
 //!
 //! There are several types of [`Rewriter`] for rewriting Request state:
 //!
 //! - [`HeaderRewriter`] rewrites named header using pattern and replacement.
-//! - [`PathRewriter`] rewrites Request path using pattern and replacement.
-//!
-//! As with [`Condition`], any function with a `Fn(Request) -> Request`
+//! - [`CookieRewriter`] sets or removes a named cookie by rebuilding the
+//!   `Cookie` header.
+//! - [`PathRewriter`] rewrites Request path using pattern and replacement,
+//!   optionally merging the incoming query string into a replacement that
+//!   carries its own via `with_query_string_append`, the same as Apache
+//!   `mod_rewrite`'s `[QSA]` flag.
+//! - [`RouteRewriter`] rewrites Request path and query from a named segment
+//!   pattern, interpolating `{name}` captures into the replacement target
+//!   and recording them as Request attributes for later stages to read.
+//! - [`RedirectRewriter`] always responds directly with a redirect, instead
+//!   of producing a new Request to continue dispatching.
+//! - [`QueryRewriter`] adds, replaces, or removes a named query parameter
+//!   over the request url's parsed query pairs, with an optional `[QSA]`-
+//!   style append mode, or rewrites the whole raw query string by pattern
+//!   and replacement the same way [`PathRewriter`] rewrites the path.
+//! - [`TryFilesRewriter`] tries a list of `$uri`-templated candidate paths
+//!   in order, rewriting to the first that exists under the docroot, or to
+//!   a fallback script (e.g. a PHP front controller) if none do - the same
+//!   shape as nginx's `try_files` directive.
+//! - [`RewriteChain`] composes an ordered list of condition-guarded rules,
+//!   re-evaluating from the top after a change the same way `mod_rewrite`
+//!   restarts per-directory processing, up to a configurable maximum number
+//!   of passes.
+//!
+//! As with [`Condition`], any function with a `Fn(Request, &Path) -> Result<RewriteOutcome, RequestBuilderException>`
 //! signature may also be used anywhere a [`Rewriter`] is accepted. This allows
-//! any custom logic to be used to produce a rewritten Request. Because a
+//! any custom logic to be used to produce a [`RewriteOutcome`]. Because a
 //! Request may be dispatched to any thread, these functions must be
 //! `Send + Sync`.
 //!
 //! ```
-//! # use lang_handler::{Request, RequestBuilderException, rewrite::Rewriter};
-//! let rewriter = |request: Request| -> Result<Request, RequestBuilderException> {
-//!   request.extend()
+//! # use std::path::Path;
+//! # use lang_handler::{Request, RequestBuilderException, rewrite::{Rewriter, RewriteOutcome}};
+//! let rewriter = |request: Request, _docroot: &Path| -> Result<RewriteOutcome, RequestBuilderException> {
+//!   let request = request.extend()
 //!     .url("http://example.com/rewritten")
-//!     .build()
+//!     .build()?;
+//!
+//!   Ok(RewriteOutcome::Matched { request, last: false })
 //! };
 //! ```
 //!
 //! Multiple Rewriters may be sequenced using `rewriter.then(other)` to apply
-//! in order.
+//! in order. A [`RewriteOutcome`] that [`RewriteOutcome::is_last`] - a
+//! `Matched` outcome with the `L` flag set, or any terminal outcome such as
+//! [`RewriteOutcome::Redirect`], [`RewriteOutcome::Forbidden`] or
+//! [`RewriteOutcome::Respond`] - stops the rest of the sequence from running,
+//! the same as Apache's `mod_rewrite` `[L]` flag.
+//!
+//! Callers driving a [`Rewriter`] (such as an embedding layer dispatching a
+//! request to a handler) are responsible for checking [`RewriteOutcome::Respond`]
+//! and returning that response directly instead of continuing on to the
+//! handler - this crate only builds the outcome, it has no handler of its
+//! own to short-circuit.
 //!
 //! # Combining Conditions and Rewriters
 //!
@@ -114,8 +173,12 @@
 
 mod condition;
 mod conditional_rewriter;
+mod match_context;
+mod pattern;
 mod rewriter;
 
 pub use condition::*;
 pub use conditional_rewriter::ConditionalRewriter;
+pub use match_context::MatchContext;
+pub use pattern::PathPattern;
 pub use rewriter::*;
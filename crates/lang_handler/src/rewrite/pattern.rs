@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use regex::{Error, Regex};
+
+/// Compiles an actix/Rocket-style path pattern such as `/user/{id}/posts/{slug}`
+/// into an anchored regex with named capture groups, splitting on `/` so a
+/// `{name}` segment can never cross a `/`.
+///
+/// - `{name}` matches a single path segment (`[^/]+`).
+/// - `{name:regex}` matches the segment using the given regex instead.
+/// - A trailing `{name:*}` captures the remainder of the path, including `/`.
+///
+/// Used by [`RouteCondition`](crate::rewrite::RouteCondition) and
+/// [`RouteRewriter`](crate::rewrite::RouteRewriter) to support named segment
+/// matching, as an alternative to [`PathCondition`](crate::rewrite::PathCondition)
+/// and [`PathRewriter`](crate::rewrite::PathRewriter)'s raw regex patterns.
+///
+/// # Examples
+///
+/// ```
+/// use lang_handler::rewrite::PathPattern;
+///
+/// let pattern = PathPattern::new("/user/{id}/posts/{slug}")
+///   .expect("should compile pattern");
+///
+/// let captures = pattern.captures("/user/42/posts/hello-world")
+///   .expect("should match path");
+///
+/// assert_eq!(captures.get("id").map(String::as_str), Some("42"));
+/// assert_eq!(captures.get("slug").map(String::as_str), Some("hello-world"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathPattern {
+  regex: Regex,
+}
+
+impl PathPattern {
+  /// Compiles the given segment pattern into a [`PathPattern`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::rewrite::PathPattern;
+  ///
+  /// let pattern = PathPattern::new("/user/{id:[0-9]+}")
+  ///   .expect("should compile pattern");
+  ///
+  /// assert!(pattern.is_match("/user/42"));
+  /// assert!(!pattern.is_match("/user/abc"));
+  /// ```
+  pub fn new(pattern: &str) -> Result<Self, Error> {
+    let mut source = String::from("^");
+
+    for (index, segment) in pattern.split('/').enumerate() {
+      if index > 0 {
+        source.push('/');
+      }
+
+      match segment
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+      {
+        Some(capture) => {
+          let (name, spec) = match capture.split_once(':') {
+            Some((name, spec)) => (name, Some(spec)),
+            None => (capture, None),
+          };
+
+          match spec {
+            Some("*") => source.push_str(&format!("(?P<{}>.*)", name)),
+            Some(spec) => source.push_str(&format!("(?P<{}>{})", name, spec)),
+            None => source.push_str(&format!("(?P<{}>[^/]+)", name)),
+          }
+        }
+        None => source.push_str(&regex::escape(segment)),
+      }
+    }
+
+    source.push('$');
+
+    Ok(Self {
+      regex: Regex::new(&source)?,
+    })
+  }
+
+  /// Returns whether the given path matches the pattern.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::rewrite::PathPattern;
+  ///
+  /// let pattern = PathPattern::new("/user/{id}").expect("should compile pattern");
+  ///
+  /// assert!(pattern.is_match("/user/42"));
+  /// assert!(!pattern.is_match("/user/42/posts"));
+  /// ```
+  pub fn is_match(&self, path: &str) -> bool {
+    self.regex.is_match(path)
+  }
+
+  /// Matches the given path, returning the named captures if it matches.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::rewrite::PathPattern;
+  ///
+  /// let pattern = PathPattern::new("/user/{id}/posts/{tail:*}")
+  ///   .expect("should compile pattern");
+  ///
+  /// let captures = pattern.captures("/user/42/posts/2024/hello")
+  ///   .expect("should match path");
+  ///
+  /// assert_eq!(captures.get("id").map(String::as_str), Some("42"));
+  /// assert_eq!(captures.get("tail").map(String::as_str), Some("2024/hello"));
+  ///
+  /// assert!(pattern.captures("/user/42").is_none());
+  /// ```
+  pub fn captures(&self, path: &str) -> Option<HashMap<String, String>> {
+    let captures = self.regex.captures(path)?;
+
+    Some(
+      self
+        .regex
+        .capture_names()
+        .flatten()
+        .filter_map(|name| {
+          captures
+            .name(name)
+            .map(|value| (name.to_string(), value.as_str().to_string()))
+        })
+        .collect(),
+    )
+  }
+}
+
+/// Replaces each `{name}` placeholder in `template` with its captured value,
+/// leaving unrecognized placeholders untouched. Shared by [`super::RouteRewriter`]
+/// and [`super::RedirectRewriter`] to interpolate captures into replacement
+/// templates.
+pub(crate) fn interpolate(template: &str, captures: &HashMap<String, String>) -> String {
+  let mut output = String::with_capacity(template.len());
+  let mut rest = template;
+
+  while let Some(start) = rest.find('{') {
+    output.push_str(&rest[..start]);
+    rest = &rest[start + 1..];
+
+    match rest.find('}') {
+      Some(end) => {
+        let name = &rest[..end];
+        match captures.get(name) {
+          Some(value) => output.push_str(value),
+          None => output.push_str(&format!("{{{}}}", name)),
+        }
+        rest = &rest[end + 1..];
+      }
+      None => {
+        output.push('{');
+        break;
+      }
+    }
+  }
+
+  output.push_str(rest);
+  output
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::{Captures, Condition, MatchContext};
+
+/// Apache `mod_rewrite` defaults `LimitInternalRecursion` to 10 passes over a
+/// rule set before giving up; [`RewriteChain`] uses the same default.
+const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+struct ChainRule {
+  conditions: Vec<Box<dyn Condition>>,
+  rewriter: Box<dyn Rewriter>,
+  last: bool,
+}
+
+impl ChainRule {
+  fn matches(&self, request: &Request, docroot: &Path) -> Option<Captures> {
+    // All guarding conditions must match; as with `ConditionGroup::And`,
+    // only the last matching condition's captures are carried forward.
+    let mut captures = Captures::empty();
+    for condition in &self.conditions {
+      captures = condition.matches(request, docroot)?;
+    }
+    Some(captures)
+  }
+}
+
+/// An ordered list of `(conditions, rewriter)` rules evaluated against a
+/// single request, feeding each rewritten request into the next rule so
+/// transformations accumulate - the same shape as Apache `mod_rewrite`'s
+/// per-directory rule processing.
+///
+/// Rules are re-evaluated from the top after any rule in the pass produces a
+/// change, the same way `mod_rewrite` restarts per-directory processing
+/// after a match, until either a pass makes no further change, a rule marked
+/// [`last`](RewriteChainBuilder::last) matches, or a rule's own
+/// [`RewriteOutcome::is_last`] outcome (e.g. a `RewriteRule` with the `L`
+/// flag) fires. [`max_iterations`](RewriteChainBuilder::max_iterations)
+/// bounds how many passes are allowed, to catch rules that rewrite a request
+/// back and forth forever; exceeding it is a
+/// [`RequestBuilderException::TooManyRewrites`].
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::{
+/// #   rewrite::{Rewriter, RewriteOutcome, RewriteChain, PathCondition, PathRewriter},
+/// #   Request,
+/// # };
+/// # let docroot = std::env::temp_dir();
+/// let chain = RewriteChain::builder()
+///   .when(PathCondition::new("^/old/(.*)$").expect("should be valid regex"))
+///   .then(PathRewriter::new("^/old/(.*)$", "/new/$1").expect("should be valid regex"))
+///   .then(PathRewriter::new("^/new/([^.]+)$", "/new/$1.php").expect("should be valid regex"))
+///   .build();
+///
+/// let request = Request::builder()
+///   .url("http://example.com/old/page")
+///   .build()
+///   .expect("should build request");
+///
+/// match chain.rewrite(request, &docroot).expect("should rewrite request") {
+///   RewriteOutcome::Matched { request, .. } => {
+///     assert_eq!(request.url().path(), "/new/page.php");
+///   }
+///   other => panic!("expected a match, got {:?}", other),
+/// }
+/// ```
+pub struct RewriteChain {
+  rules: Vec<ChainRule>,
+  max_iterations: usize,
+}
+
+impl RewriteChain {
+  /// Starts building a RewriteChain.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{RewriteChain, PathRewriter};
+  /// let chain = RewriteChain::builder()
+  ///   .then(PathRewriter::new("^(.*)$", "/index.php").expect("should be valid regex"))
+  ///   .build();
+  /// ```
+  pub fn builder() -> RewriteChainBuilder {
+    RewriteChainBuilder::new()
+  }
+}
+
+impl Rewriter for RewriteChain {
+  /// Evaluates rules in order against `request`, feeding each rewritten
+  /// request into the next rule, re-evaluating from the top after any pass
+  /// that produces a change, until the chain settles, a rule's outcome is
+  /// last, or [`max_iterations`](RewriteChainBuilder::max_iterations) is
+  /// exceeded.
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let mut request = request;
+    let mut changed_ever = false;
+
+    for _ in 0..self.max_iterations {
+      let mut changed = false;
+
+      for rule in &self.rules {
+        let Some(captures) = rule.matches(&request, docroot) else {
+          continue;
+        };
+
+        let context = MatchContext::new(captures);
+        let outcome = rule.rewriter.rewrite_with(request.clone(), docroot, &context)?;
+
+        let (next, is_last) = match outcome {
+          RewriteOutcome::Unmatched => continue,
+          RewriteOutcome::Matched { request, last } => (request, last || rule.last),
+          terminal => return Ok(terminal),
+        };
+
+        request = next;
+        changed = true;
+        changed_ever = true;
+
+        if is_last {
+          return Ok(RewriteOutcome::Matched { request, last: true });
+        }
+      }
+
+      if !changed {
+        return Ok(if changed_ever {
+          RewriteOutcome::Matched { request, last: false }
+        } else {
+          RewriteOutcome::Unmatched
+        });
+      }
+    }
+
+    Err(RequestBuilderException::TooManyRewrites(self.max_iterations))
+  }
+}
+
+/// Builds a [`RewriteChain`].
+pub struct RewriteChainBuilder {
+  rules: Vec<ChainRule>,
+  pending: Vec<Box<dyn Condition>>,
+  max_iterations: usize,
+}
+
+impl RewriteChainBuilder {
+  fn new() -> Self {
+    Self {
+      rules: Vec::new(),
+      pending: Vec::new(),
+      max_iterations: DEFAULT_MAX_ITERATIONS,
+    }
+  }
+
+  /// Adds a condition that must match for the next rule added with
+  /// [`then`](Self::then) to apply. Multiple calls before a `then` require
+  /// all of them to match, the same as stacking `RewriteCond` directives
+  /// before a `RewriteRule`.
+  pub fn when<C>(mut self, condition: Box<C>) -> Self
+  where
+    C: Condition + 'static,
+  {
+    self.pending.push(condition);
+    self
+  }
+
+  /// Adds `rewriter` as the next rule, guarded by any conditions queued
+  /// since the last `then`. A rule added with no preceding `when` always
+  /// applies.
+  pub fn then<R>(mut self, rewriter: Box<R>) -> Self
+  where
+    R: Rewriter + 'static,
+  {
+    self.rules.push(ChainRule {
+      conditions: std::mem::take(&mut self.pending),
+      rewriter,
+      last: false,
+    });
+    self
+  }
+
+  /// Marks the most recently added rule as `last`: if it matches and
+  /// rewrites the request, the chain stops immediately instead of
+  /// re-evaluating from the top, the same as Apache `mod_rewrite`'s `[L]`
+  /// flag. Has no effect if no rule has been added yet.
+  pub fn last(mut self) -> Self {
+    if let Some(rule) = self.rules.last_mut() {
+      rule.last = true;
+    }
+    self
+  }
+
+  /// Overrides the maximum number of passes over the rule set before giving
+  /// up with [`RequestBuilderException::TooManyRewrites`]. Defaults to 10,
+  /// the same as Apache `mod_rewrite`'s default `LimitInternalRecursion`.
+  pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+    self.max_iterations = max_iterations;
+    self
+  }
+
+  /// Builds the RewriteChain.
+  pub fn build(self) -> Box<RewriteChain> {
+    Box::new(RewriteChain {
+      rules: self.rules,
+      max_iterations: self.max_iterations,
+    })
+  }
+}
@@ -1,10 +1,10 @@
 use std::path::Path;
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
 
 impl<F> Rewriter for F
 where
-  F: Fn(Request, &Path) -> Result<Request, RequestBuilderException> + Sync + Send,
+  F: Fn(Request, &Path) -> Result<RewriteOutcome, RequestBuilderException> + Sync + Send,
 {
   /// Rewrites the request by calling the Fn(&Request) with the given request
   ///
@@ -12,12 +12,15 @@ where
   ///
   /// ```
   /// # use std::path::Path;
-  /// # use lang_handler::{Request, rewrite::Rewriter};
+  /// # use lang_handler::{Request, rewrite::{Rewriter, RewriteOutcome}};
   /// # let docroot = std::env::temp_dir();
-  /// let rewriter = |request: Request, docroot: &Path| {
-  ///   request.extend()
+  /// let rewriter = |request: Request, _docroot: &Path| {
+  ///   let request = request.extend()
   ///     .url("http://example.com/foo/bar")
-  ///     .build()
+  ///     .expect("should parse url")
+  ///     .build()?;
+  ///
+  ///   Ok(RewriteOutcome::Matched { request, last: false })
   /// };
   ///
   /// let request = Request::builder()
@@ -25,12 +28,14 @@ where
   ///   .build()
   ///   .expect("request should build");
   ///
-  /// let new_request = rewriter.rewrite(request, &docroot)
-  ///   .expect("rewriting should succeed");
-  ///
-  /// assert_eq!(new_request.url().path(), "/foo/bar".to_string());
+  /// match rewriter.rewrite(request, &docroot).expect("rewriting should succeed") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/foo/bar".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, docroot: &Path) -> Result<Request, RequestBuilderException> {
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
     self(request, docroot)
   }
 }
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::{
+  cookie::parse_cookie_header,
+  percent::{percent_encode, EncodeSet},
+};
+
+/// What a [`CookieRewriter`] does to its named cookie.
+enum CookieAction {
+  /// Sets the cookie to a fixed value, adding it if absent or replacing its
+  /// value if present.
+  Set(String),
+
+  /// Removes the cookie entirely.
+  Remove,
+}
+
+/// Sets or removes a named cookie by rebuilding the request's `Cookie`
+/// header, rather than mutating the response-facing [`Cookie`](crate::Cookie)
+/// type, which only models outbound `Set-Cookie` values.
+///
+/// Other cookies are always preserved in their original order.
+pub struct CookieRewriter {
+  name: String,
+  action: CookieAction,
+}
+
+impl CookieRewriter {
+  /// Constructs a CookieRewriter that sets `name` to `value`, adding it if
+  /// absent or replacing its value if present.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::CookieRewriter;
+  /// let rewriter = CookieRewriter::set("session", "abc123");
+  /// ```
+  pub fn set<N, V>(name: N, value: V) -> Box<Self>
+  where
+    N: Into<String>,
+    V: Into<String>,
+  {
+    Box::new(Self {
+      name: name.into(),
+      action: CookieAction::Set(value.into()),
+    })
+  }
+
+  /// Constructs a CookieRewriter that removes `name` entirely, leaving the
+  /// request unmatched if `name` is absent.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::CookieRewriter;
+  /// let rewriter = CookieRewriter::remove("session");
+  /// ```
+  pub fn remove<N>(name: N) -> Box<Self>
+  where
+    N: Into<String>,
+  {
+    Box::new(Self {
+      name: name.into(),
+      action: CookieAction::Remove,
+    })
+  }
+}
+
+impl Rewriter for CookieRewriter {
+  /// Applies this rewriter's set/remove action to the named cookie,
+  /// preserving every other cookie's order.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, CookieRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let rewriter = CookieRewriter::set("session", "def456");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php")
+  ///   .header("Cookie", "session=abc123; theme=dark")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(
+  ///       request.headers().get("Cookie"),
+  ///       Some("session=def456; theme=dark".to_string())
+  ///     );
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let pairs = request
+      .headers()
+      .get_line("Cookie")
+      .map(|line| parse_cookie_header(&line))
+      .unwrap_or_default();
+
+    let mut matched = false;
+    let mut output: Vec<(String, String)> = Vec::with_capacity(pairs.len() + 1);
+
+    match &self.action {
+      CookieAction::Set(value) => {
+        let mut replaced = false;
+
+        for (name, existing) in pairs {
+          if name == self.name {
+            if !replaced {
+              output.push((name, value.clone()));
+              replaced = true;
+            }
+          } else {
+            output.push((name, existing));
+          }
+        }
+
+        matched = replaced;
+
+        if !matched {
+          output.push((self.name.clone(), value.clone()));
+          matched = true;
+        }
+      }
+      CookieAction::Remove => {
+        for (name, value) in pairs {
+          if name == self.name {
+            matched = true;
+          } else {
+            output.push((name, value));
+          }
+        }
+      }
+    }
+
+    if !matched {
+      return Ok(RewriteOutcome::Unmatched);
+    }
+
+    let header = output
+      .iter()
+      .map(|(name, value)| format!("{}={}", name, percent_encode(value, EncodeSet::Component)))
+      .collect::<Vec<_>>()
+      .join("; ");
+
+    let request = request.extend().header("Cookie", header).build()?;
+
+    Ok(RewriteOutcome::Matched { request, last: false })
+  }
+}
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use regex::{Error, Regex};
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
 
 /// Rewrite a request header using a given pattern and replacement.
 pub struct HeaderRewriter {
@@ -47,7 +47,7 @@ impl Rewriter for HeaderRewriter {
   /// # Examples
   ///
   /// ```
-  /// # use lang_handler::rewrite::{Rewriter, HeaderRewriter};
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, HeaderRewriter};
   /// # use lang_handler::Request;
   /// # let docroot = std::env::temp_dir();
   /// let rewriter = HeaderRewriter::new("TEST", "(foo)", "${1}bar")
@@ -59,15 +59,14 @@ impl Rewriter for HeaderRewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(
-  ///   new_request.headers().get("TEST"),
-  ///   Some("foobar".to_string())
-  /// );
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.headers().get("TEST"), Some("foobar".to_string()));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<Request, RequestBuilderException> {
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
     let HeaderRewriter {
       name,
       pattern,
@@ -75,11 +74,15 @@ impl Rewriter for HeaderRewriter {
     } = self;
 
     match request.headers().get(name) {
-      None => Ok(request),
-      Some(value) => request
-        .extend()
-        .header(name, pattern.replace(&value, replacement.clone()))
-        .build(),
+      None => Ok(RewriteOutcome::Unmatched),
+      Some(value) => {
+        let request = request
+          .extend()
+          .header(name, pattern.replace(&value, replacement.clone()))
+          .build()?;
+
+        Ok(RewriteOutcome::Matched { request, last: false })
+      }
     }
   }
 }
@@ -1,15 +1,21 @@
 use std::path::Path;
 
-use regex::{Error, Regex};
-use url::Url;
+use regex::{Error, Regex, RegexBuilder};
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteFlags, RewriteOutcome, Rewriter};
 
-/// Rewrite a request href using a given pattern and replacement.
-pub struct HrefRewriter(Regex, String);
+/// Rewrite a request href using a given pattern and replacement, optionally
+/// controlled by Apache `mod_rewrite`-style [`RewriteFlags`] such as `QSA`,
+/// `NC`, `R[=code]`, and `L`.
+pub struct HrefRewriter {
+  pattern: Regex,
+  replacement: String,
+  flags: RewriteFlags,
+}
 
 impl HrefRewriter {
-  /// Construct HrefRewriter using the provided regex pattern and replacement.
+  /// Construct HrefRewriter using the provided regex pattern and
+  /// replacement, with no flags applied.
   ///
   /// # Examples
   ///
@@ -25,19 +31,76 @@ impl HrefRewriter {
     Error: From<<R as TryInto<Regex>>::Error>,
     S: Into<String>,
   {
+    Self::with_flags(pattern, replacement, RewriteFlags::default())
+  }
+
+  /// Construct HrefRewriter using the provided regex pattern, replacement,
+  /// and [`RewriteFlags`], the same flags [`super::RewriteRule`] accepts.
+  ///
+  /// `NC` is applied to the pattern itself, at construction time; `QSA`,
+  /// `R[=code]`, and `L` are applied when the rewriter runs, in
+  /// [`rewrite`](Rewriter::rewrite).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, RewriteFlags, HrefRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let rewriter = HrefRewriter::with_flags(
+  ///   "^/ARTICLE/([0-9]+)(\\?.*)?$",
+  ///   "/index.php?id=$1",
+  ///   RewriteFlags::parse("QSA,NC").expect("should parse flags"),
+  /// ).expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/article/42?ref=homepage")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/index.php");
+  ///     assert_eq!(request.url().query(), Some("id=42&ref=homepage"));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  pub fn with_flags<R, S>(pattern: R, replacement: S, flags: RewriteFlags) -> Result<Box<Self>, Error>
+  where
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+    S: Into<String>,
+  {
+    // `TryInto<Regex>` hands over an already compiled `Regex`, so `NC` is
+    // applied by recompiling its source with `RegexBuilder`'s
+    // case-insensitive option. Without `NC` the converted `Regex` is used
+    // as is, with whatever case-sensitivity it was built with.
     let pattern = pattern.try_into()?;
-    let replacement = replacement.into();
-    Ok(Box::new(Self(pattern, replacement)))
+    let pattern = if flags.case_insensitive {
+      RegexBuilder::new(pattern.as_str())
+        .case_insensitive(true)
+        .build()?
+    } else {
+      pattern
+    };
+
+    Ok(Box::new(Self {
+      pattern,
+      replacement: replacement.into(),
+      flags,
+    }))
   }
 }
 
 impl Rewriter for HrefRewriter {
-  /// Rewrite request path using the provided regex pattern and replacement.
+  /// Rewrite request path using the provided regex pattern and replacement,
+  /// applying this rewriter's [`RewriteFlags`].
   ///
   /// # Examples
   ///
   /// ```
-  /// # use lang_handler::rewrite::{Rewriter, HrefRewriter};
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, HrefRewriter};
   /// # use lang_handler::Request;
   /// # let docroot = std::env::temp_dir();
   /// let rewriter = HrefRewriter::new("^(.*)$", "/index.php?route=$1")
@@ -48,14 +111,20 @@ impl Rewriter for HrefRewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.url().path(), "/index.php".to_string());
-  /// assert_eq!(new_request.url().query(), Some("route=/foo/bar"));
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/index.php".to_string());
+  ///     assert_eq!(request.url().query(), Some("route=/foo/bar"));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<Request, RequestBuilderException> {
-    let HrefRewriter(pattern, replacement) = self;
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let HrefRewriter {
+      pattern,
+      replacement,
+      flags,
+    } = self;
     let url = request.url();
 
     let input = {
@@ -64,23 +133,71 @@ impl Rewriter for HrefRewriter {
       let fragment = url.fragment().map_or(String::new(), |f| format!("#{}", f));
       format!("{}{}{}", path, query, fragment)
     };
-    let output = pattern.replace(&input, replacement);
+    let output = pattern.replace(&input, replacement.as_str());
 
-    // No change, return original request
+    // No change, leave the request unmatched
     if input == output {
-      return Ok(request);
+      return Ok(RewriteOutcome::Unmatched);
+    }
+
+    if flags.forbidden {
+      return Ok(RewriteOutcome::Forbidden);
     }
 
-    let base_url_string = format!("{}://{}", url.scheme(), url.authority());
-    let base_url = Url::parse(&base_url_string)
-      .map_err(|_| RequestBuilderException::UrlParseFailed(base_url_string.clone()))?;
+    if let Some(status) = flags.redirect {
+      return Ok(RewriteOutcome::Redirect {
+        status,
+        location: output.into_owned(),
+      });
+    }
+
+    // Split the rewritten href back into path/query/fragment the same way
+    // `PathRewriter` splits a rewritten path, rather than re-parsing it as a
+    // full URL - the href never carries a scheme or authority to parse.
+    let (path, rest) = match output.find(['?', '#']) {
+      Some(index) => (&output[..index], Some(&output[index..])),
+      None => (output.as_ref(), None),
+    };
+
+    let (query, fragment) = match rest {
+      Some(rest) if rest.starts_with('?') => match rest.find('#') {
+        Some(hash) => (Some(&rest[1..hash]), Some(&rest[hash + 1..])),
+        None => (Some(&rest[1..]), None),
+      },
+      Some(rest) => (None, Some(&rest[1..])),
+      None => (None, None),
+    };
+
+    let mut copy = url.clone();
+    copy.set_path(path);
+
+    // `QSA` appends the original request's query string to the rewritten
+    // target's rather than letting it discard or overwrite whatever the
+    // replacement produced, the same as `PathRewriter::with_query_string_append`.
+    if flags.query_string_append {
+      let original = request.url().query().filter(|query| !query.is_empty());
+      let merged = match (query, original) {
+        (Some(rewritten), Some(original)) => Some(format!("{rewritten}&{original}")),
+        (Some(rewritten), None) => Some(rewritten.to_string()),
+        (None, Some(original)) => Some(original.to_string()),
+        (None, None) => None,
+      };
+      copy.set_query(merged.as_deref());
+    } else {
+      copy.set_query(query);
+    }
 
-    let options = Url::options().base_url(Some(&base_url));
+    copy.set_fragment(fragment);
 
-    let copy = options.parse(output.as_ref()).map_err(|_| {
-      RequestBuilderException::UrlParseFailed(format!("{}{}", base_url_string, output))
-    })?;
+    let request = request
+      .extend()
+      .url(copy)
+      .expect("re-serialized url should re-parse")
+      .build()?;
 
-    request.extend().url(copy).build()
+    Ok(RewriteOutcome::Matched {
+      request,
+      last: flags.last,
+    })
   }
 }
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use regex::{Error, Regex};
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
 
 /// Rewrite a request header using a given pattern and replacement.
 pub struct MethodRewriter(Regex, String);
@@ -37,7 +37,7 @@ impl Rewriter for MethodRewriter {
   /// # Examples
   ///
   /// ```
-  /// # use lang_handler::rewrite::{Rewriter, MethodRewriter};
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, MethodRewriter};
   /// # use lang_handler::Request;
   /// # let docroot = std::env::temp_dir();
   /// let rewriter = MethodRewriter::new("PUT", "POST")
@@ -49,20 +49,24 @@ impl Rewriter for MethodRewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.method(), "POST".to_string());
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.method(), "POST".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<Request, RequestBuilderException> {
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
     let MethodRewriter(pattern, replacement) = self;
 
     let input = request.method();
     let output = pattern.replace(input, replacement.clone());
     if output == input {
-      return Ok(request);
+      return Ok(RewriteOutcome::Unmatched);
     }
 
-    request.extend().method(output).build()
+    let request = request.extend().method(output.into_owned()).build()?;
+
+    Ok(RewriteOutcome::Matched { request, last: false })
   }
 }
@@ -1,28 +1,60 @@
 use std::path::Path;
 
 use crate::{
-  rewrite::{Condition, ConditionalRewriter},
+  rewrite::{Condition, ConditionalRewriter, MatchContext},
   Request, RequestBuilderException,
 };
 
+mod chain;
 mod closure;
+mod cookie;
 mod header;
 mod href;
 mod method;
 mod path;
+mod query;
+mod redirect;
+mod route;
+mod rule;
 mod sequence;
+pub(crate) mod try_files;
 
+pub use chain::{RewriteChain, RewriteChainBuilder};
+pub use cookie::CookieRewriter;
 pub use header::HeaderRewriter;
 pub use href::HrefRewriter;
 pub use method::MethodRewriter;
 pub use path::PathRewriter;
+pub use query::QueryRewriter;
+pub use redirect::RedirectRewriter;
+pub use route::RouteRewriter;
+pub use rule::{RewriteFlagError, RewriteFlags, RewriteOutcome, RewriteRule};
 pub use sequence::RewriterSequence;
+pub use try_files::TryFilesRewriter;
 
-/// A Rewriter simply applies its rewrite function to produce a possibly new
-/// request object.
+/// A Rewriter applies its rewrite function to produce a [`RewriteOutcome`]:
+/// a possibly new request, a request that should stop further processing
+/// (`L` in `mod_rewrite` terms), or a terminal response (e.g. a redirect)
+/// that should be returned directly instead of continuing to a handler.
 pub trait Rewriter: Sync + Send {
   /// Rewrite a request using the rewriter's logic.
-  fn rewrite(&self, request: Request, docroot: &Path) -> Result<Request, RequestBuilderException>;
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException>;
+
+  /// Rewrite a request using the rewriter's logic, with the [`MatchContext`]
+  /// carrying the [`Captures`](crate::rewrite::Captures) of the [`Condition`]
+  /// that guarded this rewriter, if any. Rewriters that support `%1`..`%9`
+  /// backreferences - such as [`PathRewriter`] - override this to expand
+  /// them; the default implementation ignores the context and simply calls
+  /// [`rewrite`](Rewriter::rewrite).
+  fn rewrite_with(
+    &self,
+    request: Request,
+    docroot: &Path,
+    context: &MatchContext,
+  ) -> Result<RewriteOutcome, RequestBuilderException> {
+    let _ = context;
+    self.rewrite(request, docroot)
+  }
 }
 
 impl<T: ?Sized> RewriterExt for T where T: Rewriter {}
@@ -36,7 +68,7 @@ pub trait RewriterExt: Rewriter {
   /// ```
   /// # use lang_handler::{
   /// #  Request,
-  /// #  rewrite::{Rewriter, RewriterExt, PathCondition, PathRewriter}
+  /// #  rewrite::{Rewriter, RewriterExt, RewriteOutcome, PathCondition, PathRewriter}
   /// # };
   /// # let docroot = std::env::temp_dir();
   /// let rewriter = PathRewriter::new("^(/index\\.php)$", "/foo$1")
@@ -52,10 +84,12 @@ pub trait RewriterExt: Rewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = conditional_rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.url().path(), "/foo/index.php".to_string());
+  /// match conditional_rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/foo/index.php".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
   fn when<C>(self: Box<Self>, condition: Box<C>) -> Box<ConditionalRewriter<Self, C>>
   where
@@ -71,7 +105,7 @@ pub trait RewriterExt: Rewriter {
   /// ```
   /// # use lang_handler::{
   /// #   Request,
-  /// #   rewrite::{Rewriter, RewriterExt, PathRewriter, HeaderRewriter}
+  /// #   rewrite::{Rewriter, RewriterExt, RewriteOutcome, PathRewriter, HeaderRewriter}
   /// # };
   /// # let docroot = std::env::temp_dir();
   /// let first = PathRewriter::new("^(/index.php)$", "/foo$1")
@@ -88,10 +122,12 @@ pub trait RewriterExt: Rewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = sequence.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.url().path(), "/foo/bar.php".to_string());
+  /// match sequence.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/foo/bar.php".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
   fn then<R>(self: Box<Self>, rewriter: Box<R>) -> Box<RewriterSequence<Self, R>>
   where
@@ -2,12 +2,48 @@ use std::path::Path;
 
 use regex::{Error, Regex};
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::{Captures, MatchContext};
+
+/// Expands `%1`..`%9` backreferences in `template` against `captures`,
+/// mirroring Apache `mod_rewrite`'s `RewriteCond` backreference syntax.
+/// `%%` expands to a literal `%`. A missing group, or no captures at all
+/// (no enclosing Condition), expands to an empty string. Any other `%`
+/// sequence is left untouched.
+fn expand_percent(template: &str, captures: Option<&Captures>) -> String {
+  let mut output = String::with_capacity(template.len());
+  let mut chars = template.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    if c != '%' {
+      output.push(c);
+      continue;
+    }
+
+    match chars.peek() {
+      Some('%') => {
+        chars.next();
+        output.push('%');
+      }
+      Some(d) if d.is_ascii_digit() && *d != '0' => {
+        let index = d.to_digit(10).expect("digit") as usize;
+        chars.next();
+        if let Some(value) = captures.and_then(|captures| captures.get(index)) {
+          output.push_str(value);
+        }
+      }
+      _ => output.push('%'),
+    }
+  }
+
+  output
+}
 
 /// Rewrite a request path using a given pattern and replacement.
 pub struct PathRewriter {
   pattern: Regex,
   replacement: String,
+  query_string_append: bool,
 }
 
 impl PathRewriter {
@@ -32,8 +68,41 @@ impl PathRewriter {
     Ok(Box::new(Self {
       pattern,
       replacement,
+      query_string_append: false,
     }))
   }
+
+  /// Enables query-string-append (`QSA`) semantics: when `replacement`
+  /// includes a `?query`, the incoming request's query string is appended
+  /// to it instead of being discarded, the same as Apache `mod_rewrite`'s
+  /// `[QSA]` flag.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, PathRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let rewriter = PathRewriter::new("^/article/([0-9]+)$", "/index.php?id=$1")
+  ///   .expect("should be valid regex")
+  ///   .with_query_string_append(true);
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/article/42?ref=homepage")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().query(), Some("id=42&ref=homepage"));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  pub fn with_query_string_append(mut self: Box<Self>, append: bool) -> Box<Self> {
+    self.query_string_append = append;
+    self
+  }
 }
 
 impl Rewriter for PathRewriter {
@@ -42,7 +111,7 @@ impl Rewriter for PathRewriter {
   /// # Examples
   ///
   /// ```
-  /// # use lang_handler::rewrite::{Rewriter, PathRewriter};
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, PathRewriter};
   /// # use lang_handler::Request;
   /// # let docroot = std::env::temp_dir();
   /// let rewriter = PathRewriter::new("^(/foo)$", "/index.php")
@@ -53,28 +122,109 @@ impl Rewriter for PathRewriter {
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = rewriter.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/index.php".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    self.apply(request, docroot, None)
+  }
+
+  /// Rewrite request path as in [`rewrite`](Rewriter::rewrite), additionally
+  /// expanding `%1`..`%9` backreferences in the replacement from the
+  /// [`MatchContext`]'s captures, e.g. a guarding [`PathCondition`](crate::rewrite::PathCondition)'s
+  /// regex groups.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Condition, MatchContext, PathCondition, Rewriter, RewriteOutcome, PathRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let condition = PathCondition::new("^/user/([0-9]+)$")
+  ///   .expect("should be valid regex");
+  ///
+  /// let rewriter = PathRewriter::new("^/user/[0-9]+$", "/users/%1")
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/user/42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// let captures = condition.matches(&request, &docroot).expect("should match");
+  /// let context = MatchContext::new(captures);
   ///
-  /// assert_eq!(new_request.url().path(), "/index.php".to_string());
+  /// match rewriter.rewrite_with(request, &docroot, &context).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/users/42".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<Request, RequestBuilderException> {
+  fn rewrite_with(
+    &self,
+    request: Request,
+    docroot: &Path,
+    context: &MatchContext,
+  ) -> Result<RewriteOutcome, RequestBuilderException> {
+    self.apply(request, docroot, context.captures())
+  }
+}
+
+impl PathRewriter {
+  fn apply(
+    &self,
+    request: Request,
+    _docroot: &Path,
+    captures: Option<&Captures>,
+  ) -> Result<RewriteOutcome, RequestBuilderException> {
     let PathRewriter {
       pattern,
       replacement,
+      query_string_append,
     } = self;
 
+    let replacement = expand_percent(replacement, captures);
+
     let input = request.url().path();
-    let output = pattern.replace(input, replacement.clone());
+    let output = pattern.replace(input, replacement.as_str());
 
-    // No change, return original request
+    // No change, leave the request unmatched
     if input == output {
-      return Ok(request);
+      return Ok(RewriteOutcome::Unmatched);
     }
 
+    let (path, query) = match output.find('?') {
+      Some(index) => (&output[..index], Some(&output[index + 1..])),
+      None => (output.as_ref(), None),
+    };
+
     let mut copy = request.url().clone();
-    copy.set_path(output.as_ref());
+    copy.set_path(path);
+
+    if *query_string_append {
+      let original = request.url().query().filter(|query| !query.is_empty());
+      let merged = match (query, original) {
+        (Some(query), Some(original)) => Some(format!("{query}&{original}")),
+        (Some(query), None) => Some(query.to_string()),
+        (None, Some(original)) => Some(original.to_string()),
+        (None, None) => None,
+      };
+      copy.set_query(merged.as_deref());
+    } else if let Some(query) = query {
+      copy.set_query(Some(query));
+    }
+
+    let request = request
+      .extend()
+      .url(copy)
+      .expect("re-serialized url should re-parse")
+      .build()?;
 
-    request.extend().url(copy).build()
+    Ok(RewriteOutcome::Matched { request, last: false })
   }
 }
@@ -0,0 +1,280 @@
+use std::path::Path;
+
+use regex::{Error, Regex};
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::pattern::interpolate;
+
+/// What a [`QueryRewriter`] does to its named query parameter, or to the
+/// request's raw query string as a whole.
+enum QueryAction {
+  /// Sets the parameter to an interpolated value template, adding it if
+  /// absent or replacing its value if present.
+  Set(String),
+
+  /// Replaces the parameter's existing value using a regex pattern and
+  /// replacement template; a no-op if the parameter is absent.
+  Replace(Regex, String),
+
+  /// Removes the parameter entirely.
+  Remove,
+
+  /// Replaces the raw query string using a regex pattern and replacement
+  /// template, the same as [`super::PathRewriter`] does for the request
+  /// path; a no-op if the pattern doesn't match.
+  RewriteAll(Regex, String),
+}
+
+/// Adds, replaces, or removes a named query parameter over the request
+/// url's parsed query pairs, rather than the raw query string. A rewriter
+/// built with [`QueryRewriter::pattern`] instead rewrites the raw query
+/// string as a whole.
+///
+/// Other query parameters are always preserved in their original order,
+/// the same as Apache `mod_rewrite`'s `[QSA]` flag preserves the incoming
+/// query string instead of discarding it.
+pub struct QueryRewriter {
+  name: String,
+  action: QueryAction,
+  append: bool,
+}
+
+impl QueryRewriter {
+  /// Constructs a QueryRewriter that sets `name` to the interpolated
+  /// `value` template (see [`super::RouteRewriter`] for `{name}`
+  /// placeholder syntax), adding it if absent, or replacing its first
+  /// occurrence and dropping any later occurrences if present.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryRewriter;
+  /// let rewriter = QueryRewriter::set("page", "1");
+  /// ```
+  pub fn set<N, S>(name: N, value: S) -> Box<Self>
+  where
+    N: Into<String>,
+    S: Into<String>,
+  {
+    Box::new(Self {
+      name: name.into(),
+      action: QueryAction::Set(value.into()),
+      append: false,
+    })
+  }
+
+  /// Constructs a QueryRewriter that replaces `name`'s existing value using
+  /// the given regex pattern and replacement template, leaving the request
+  /// unmatched if `name` is absent.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryRewriter;
+  /// let rewriter = QueryRewriter::replace("sort", "^(asc|desc)$", "$1c")
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn replace<N, R, S>(name: N, pattern: R, replacement: S) -> Result<Box<Self>, Error>
+  where
+    N: Into<String>,
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+    S: Into<String>,
+  {
+    let pattern = pattern.try_into()?;
+
+    Ok(Box::new(Self {
+      name: name.into(),
+      action: QueryAction::Replace(pattern, replacement.into()),
+      append: false,
+    }))
+  }
+
+  /// Constructs a QueryRewriter that removes `name` entirely, leaving the
+  /// request unmatched if `name` is absent.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryRewriter;
+  /// let rewriter = QueryRewriter::remove("token");
+  /// ```
+  pub fn remove<N>(name: N) -> Box<Self>
+  where
+    N: Into<String>,
+  {
+    Box::new(Self {
+      name: name.into(),
+      action: QueryAction::Remove,
+      append: false,
+    })
+  }
+
+  /// Constructs a QueryRewriter that replaces the request's entire raw query
+  /// string using the given regex pattern and replacement template, the
+  /// same as [`super::PathRewriter`] does for the request path, leaving the
+  /// request unmatched if the pattern doesn't match.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryRewriter;
+  /// let rewriter = QueryRewriter::pattern("^page=(\\d+)$", "p=$1")
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn pattern<R, S>(pattern: R, replacement: S) -> Result<Box<Self>, Error>
+  where
+    R: TryInto<Regex>,
+    Error: From<<R as TryInto<Regex>>::Error>,
+    S: Into<String>,
+  {
+    let pattern = pattern.try_into()?;
+
+    Ok(Box::new(Self {
+      name: String::new(),
+      action: QueryAction::RewriteAll(pattern, replacement.into()),
+      append: false,
+    }))
+  }
+
+  /// Enables query-string-append (`QSA`) semantics for [`QueryRewriter::set`]:
+  /// adds a new pair alongside any existing occurrences of `name` instead of
+  /// replacing the first one. Has no effect on `replace`/`remove`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::QueryRewriter;
+  /// let rewriter = QueryRewriter::set("tag", "new").with_query_string_append(true);
+  /// ```
+  pub fn with_query_string_append(mut self: Box<Self>, append: bool) -> Box<Self> {
+    self.append = append;
+    self
+  }
+}
+
+impl Rewriter for QueryRewriter {
+  /// Applies this rewriter's add/replace/remove action to the named query
+  /// parameter, preserving every other pair's order and any repeated keys.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, QueryRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let rewriter = QueryRewriter::set("page", "2");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/index.php?page=1&sort=asc")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().query(), Some("page=2&sort=asc"));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    if let QueryAction::RewriteAll(pattern, replacement) = &self.action {
+      let input = request.url().query().unwrap_or("");
+      let output = pattern.replace(input, replacement.as_str());
+
+      // No change, leave the request unmatched
+      if input == output {
+        return Ok(RewriteOutcome::Unmatched);
+      }
+
+      let mut url = request.url().clone();
+      url.set_query(if output.is_empty() { None } else { Some(output.as_ref()) });
+
+      let request = request.extend().url(url).expect("re-serialized url should re-parse").build()?;
+
+      return Ok(RewriteOutcome::Matched { request, last: false });
+    }
+
+    let pairs: Vec<(String, String)> = request
+      .url()
+      .query_pairs()
+      .map(|(key, value)| (key.into_owned(), value.into_owned()))
+      .collect();
+
+    let mut matched = false;
+    let mut output: Vec<(String, String)> = Vec::with_capacity(pairs.len() + 1);
+
+    match &self.action {
+      QueryAction::Set(value) => {
+        let value = interpolate(value, request.attributes());
+
+        if self.append {
+          output = pairs;
+        } else {
+          let mut replaced = false;
+
+          for (key, existing) in pairs {
+            if key == self.name {
+              if !replaced {
+                output.push((key, value.clone()));
+                replaced = true;
+              }
+            } else {
+              output.push((key, existing));
+            }
+          }
+
+          matched = replaced;
+        }
+
+        if self.append || !matched {
+          output.push((self.name.clone(), value));
+          matched = true;
+        }
+      }
+      QueryAction::Replace(pattern, replacement) => {
+        for (key, value) in pairs {
+          if key == self.name {
+            matched = true;
+            output.push((key, pattern.replace(&value, replacement.as_str()).into_owned()));
+          } else {
+            output.push((key, value));
+          }
+        }
+      }
+      QueryAction::Remove => {
+        for (key, value) in pairs {
+          if key == self.name {
+            matched = true;
+          } else {
+            output.push((key, value));
+          }
+        }
+      }
+      QueryAction::RewriteAll(..) => unreachable!("handled above"),
+    }
+
+    if !matched {
+      return Ok(RewriteOutcome::Unmatched);
+    }
+
+    let mut url = request.url().clone();
+
+    if output.is_empty() {
+      url.set_query(None);
+    } else {
+      url
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(output.iter().map(|(key, value)| (key.as_str(), value.as_str())));
+    }
+
+    let request = request
+      .extend()
+      .url(url)
+      .expect("re-serialized url should re-parse")
+      .build()?;
+
+    Ok(RewriteOutcome::Matched { request, last: false })
+  }
+}
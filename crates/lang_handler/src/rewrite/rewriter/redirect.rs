@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::pattern::interpolate;
+use crate::Response;
+
+/// Responds with an HTTP redirect instead of continuing the rewrite chain.
+///
+/// Unconditionally produces a [`RewriteOutcome::Respond`] carrying a
+/// redirect [`Response`] (status plus a `Location` header), built from
+/// `location` with `{name}` placeholders interpolated from the request's
+/// attributes (see [`Request::attribute`]) - e.g. captures recorded by a
+/// preceding [`super::RouteRewriter`] in a [`super::RewriterSequence`].
+///
+/// Combine with [`crate::rewrite::RewriterExt`] via `.when(condition)` to
+/// gate when the redirect applies, the same as any other [`Rewriter`].
+pub struct RedirectRewriter {
+  status: u16,
+  location: String,
+}
+
+impl RedirectRewriter {
+  /// Construct a new RedirectRewriter responding with `status` and a
+  /// `Location` header built from `location`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::RedirectRewriter;
+  /// let rewriter = RedirectRewriter::new(301, "/new/{path}");
+  /// ```
+  pub fn new<S>(status: u16, location: S) -> Box<Self>
+  where
+    S: Into<String>,
+  {
+    Box::new(Self {
+      status,
+      location: location.into(),
+    })
+  }
+}
+
+impl Rewriter for RedirectRewriter {
+  /// Responds with a redirect to the interpolated `Location`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, RouteRewriter, RewriterExt, RedirectRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let route = RouteRewriter::new("/user/{id}", "/profile/{id}")
+  ///   .expect("should be valid pattern");
+  ///
+  /// let redirect = RedirectRewriter::new(301, "/profile/{id}");
+  ///
+  /// let sequence = route.then(redirect);
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/user/42")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match sequence.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Respond(response) => {
+  ///     assert_eq!(response.status(), 301);
+  ///     assert_eq!(response.headers().get("Location"), Some("/profile/42".to_string()));
+  ///   }
+  ///   other => panic!("expected a redirect response, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let location = interpolate(&self.location, request.attributes());
+
+    let response = Response::builder()
+      .status(self.status as i32)
+      .header("Location", location)
+      .build();
+
+    Ok(RewriteOutcome::Respond(response))
+  }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use regex::Error;
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::{pattern::interpolate, PathPattern};
+
+/// Rewrites a request whose path matches a named segment pattern, such as
+/// `/user/{id}/posts/{slug}`, substituting `{name}` placeholders in `target`
+/// with the corresponding captured segment.
+///
+/// Every capture is also recorded as a request attribute (see
+/// [`Request::attribute`]), so later rewriters in a [`super::RewriterSequence`]
+/// - and the eventual request handler - can read them back by name, rather
+/// than only through the rewritten URL.
+///
+/// If the path does not match the pattern, the outcome is
+/// [`RewriteOutcome::Unmatched`], the same as [`super::PathRewriter`] gives
+/// for a non-matching regex.
+pub struct RouteRewriter {
+  pattern: PathPattern,
+  target: String,
+}
+
+impl RouteRewriter {
+  /// Construct a new RouteRewriter matching the Request path against the
+  /// given segment pattern, rewriting it to `target` on a match.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RouteRewriter};
+  /// let rewriter = RouteRewriter::new("/user/{id}", "/index.php?user={id}")
+  ///   .expect("should be valid pattern");
+  /// ```
+  pub fn new<S>(pattern: &str, target: S) -> Result<Box<Self>, Error>
+  where
+    S: Into<String>,
+  {
+    Ok(Box::new(Self {
+      pattern: PathPattern::new(pattern)?,
+      target: target.into(),
+    }))
+  }
+}
+
+impl Rewriter for RouteRewriter {
+  /// Rewrite request path and query using the matched segment captures.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{Rewriter, RewriteOutcome, RouteRewriter};
+  /// # use lang_handler::Request;
+  /// # let docroot = std::env::temp_dir();
+  /// let rewriter = RouteRewriter::new("/user/{id}/posts/{slug}", "/index.php?user={id}&post={slug}")
+  ///   .expect("should be valid pattern");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/user/42/posts/hello-world")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/index.php".to_string());
+  ///     assert_eq!(request.url().query(), Some("user=42&post=hello-world"));
+  ///     assert_eq!(request.attribute("id"), Some("42"));
+  ///     assert_eq!(request.attribute("slug"), Some("hello-world"));
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, _docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let Some(captures) = self.pattern.captures(request.url().path()) else {
+      return Ok(RewriteOutcome::Unmatched);
+    };
+
+    let (path_template, query_template) = match self.target.split_once('?') {
+      Some((path, query)) => (path, Some(query)),
+      None => (self.target.as_str(), None),
+    };
+
+    let mut url = request.url().clone();
+    url.set_path(&interpolate(path_template, &captures));
+    url.set_query(query_template.map(|query| interpolate(query, &captures)).as_deref());
+
+    let mut builder = request.extend();
+
+    for (name, value) in &captures {
+      builder = builder.attribute(name.clone(), value.clone());
+    }
+
+    let request = builder
+      .url(url)
+      .expect("re-serialized url should re-parse")
+      .build()?;
+
+    Ok(RewriteOutcome::Matched { request, last: false })
+  }
+}
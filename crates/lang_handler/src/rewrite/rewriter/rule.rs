@@ -0,0 +1,292 @@
+use std::path::Path;
+
+use regex::{Error, Regex, RegexBuilder};
+
+use super::{Request, RequestBuilderException, Rewriter};
+use crate::Response;
+
+/// Apache `mod_rewrite`-style flags controlling how a matched [`RewriteRule`]
+/// is applied.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RewriteFlags {
+  /// `L` - stop processing further rules once this one matches.
+  pub last: bool,
+
+  /// `R[=code]` - respond with an external redirect using the given status
+  /// (defaulting to 302) instead of rewriting internally.
+  pub redirect: Option<u16>,
+
+  /// `F` - respond 403 Forbidden instead of rewriting internally.
+  pub forbidden: bool,
+
+  /// `QSA` - append the original request's query string to the rewritten
+  /// URL's query string.
+  pub query_string_append: bool,
+
+  /// `NC` - match the pattern case-insensitively.
+  pub case_insensitive: bool,
+}
+
+/// Error produced when parsing an invalid Apache-style rewrite flag list.
+#[derive(Debug, PartialEq)]
+pub enum RewriteFlagError {
+  /// A flag name was not recognized.
+  UnknownFlag(String),
+
+  /// An `R=code` value could not be parsed as a status code.
+  InvalidRedirectCode(String),
+}
+
+impl std::fmt::Display for RewriteFlagError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RewriteFlagError::UnknownFlag(flag) => write!(f, "Unknown rewrite flag: \"{}\"", flag),
+      RewriteFlagError::InvalidRedirectCode(code) => {
+        write!(f, "Invalid redirect status code: \"{}\"", code)
+      }
+    }
+  }
+}
+
+impl RewriteFlags {
+  /// Parses a comma-separated Apache-style flag list, e.g. `"L,R=301,QSA"`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::RewriteFlags;
+  /// let flags = RewriteFlags::parse("L,R=301,QSA").expect("should parse");
+  ///
+  /// assert!(flags.last);
+  /// assert_eq!(flags.redirect, Some(301));
+  /// assert!(flags.query_string_append);
+  /// ```
+  pub fn parse(flags: &str) -> Result<Self, RewriteFlagError> {
+    let mut parsed = RewriteFlags::default();
+
+    for flag in flags.split(',').map(str::trim).filter(|flag| !flag.is_empty()) {
+      let mut parts = flag.splitn(2, '=');
+      let name = parts.next().unwrap_or("").to_ascii_uppercase();
+
+      match name.as_str() {
+        "L" => parsed.last = true,
+        "F" => parsed.forbidden = true,
+        "QSA" => parsed.query_string_append = true,
+        "NC" => parsed.case_insensitive = true,
+        "R" => {
+          parsed.redirect = Some(match parts.next() {
+            Some(code) => code
+              .parse::<u16>()
+              .map_err(|_| RewriteFlagError::InvalidRedirectCode(code.to_string()))?,
+            None => 302,
+          });
+        }
+        _ => return Err(RewriteFlagError::UnknownFlag(flag.to_string())),
+      }
+    }
+
+    Ok(parsed)
+  }
+}
+
+/// The outcome of evaluating a [`Rewriter`] against a request, most notably
+/// [`RewriteRule::apply`], but also the result of the generic [`Rewriter`]
+/// trait, so any rewriter can short-circuit a [`super::RewriterSequence`] or
+/// respond directly instead of producing a new request.
+#[derive(Clone, Debug)]
+pub enum RewriteOutcome {
+  /// The rewriter did not apply - the request passes through unchanged.
+  Unmatched,
+
+  /// The rewriter matched and produced a rewritten request. `last` mirrors
+  /// the `L` flag, signalling that a rule chain should stop evaluating
+  /// further rules once it sees this.
+  Matched {
+    /// The rewritten request.
+    request: Request,
+    /// Whether the `L` flag was set on the matching rule.
+    last: bool,
+  },
+
+  /// The rule's `R[=code]` flag fired; the caller should respond with an
+  /// external redirect to `location` using `status` instead of continuing.
+  Redirect {
+    /// The redirect status code, e.g. 301 or 302.
+    status: u16,
+    /// The rewritten location to redirect to.
+    location: String,
+  },
+
+  /// The rule's `F` flag fired; the caller should respond 403 Forbidden
+  /// instead of continuing.
+  Forbidden,
+
+  /// The rewriter wants to respond directly with `response` instead of the
+  /// request continuing on to a handler, e.g. a rewriter serving a cached
+  /// response for a matched path.
+  Respond(Response),
+}
+
+impl RewriteOutcome {
+  /// Returns whether this outcome should stop a rule chain from evaluating
+  /// any further rewriters - either because it's a terminal response
+  /// ([`RewriteOutcome::Redirect`], [`RewriteOutcome::Forbidden`],
+  /// [`RewriteOutcome::Respond`]), or because it matched with the `L` flag
+  /// set.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::RewriteOutcome;
+  /// assert!(!RewriteOutcome::Unmatched.is_last());
+  /// assert!(RewriteOutcome::Forbidden.is_last());
+  /// ```
+  pub fn is_last(&self) -> bool {
+    match self {
+      RewriteOutcome::Unmatched => false,
+      RewriteOutcome::Matched { last, .. } => *last,
+      RewriteOutcome::Redirect { .. } | RewriteOutcome::Forbidden | RewriteOutcome::Respond(_) => true,
+    }
+  }
+}
+
+/// A `mod_rewrite`-style rule: a regex matched and substituted against the
+/// request path, with Apache-like flags controlling what happens next.
+///
+/// The substitution may reference capture groups from the pattern using
+/// `$1`, `$2`, etc., the same as [`super::PathRewriter`].
+///
+/// Combine with [`crate::rewrite::RewriterExt`] via `.when(condition)` to
+/// gate whether the rule is attempted at all — `RewriteRule` itself only
+/// adds the substitution and flag semantics `mod_rewrite` layers on top of
+/// plain path matching.
+pub struct RewriteRule {
+  pattern: Regex,
+  substitution: String,
+  flags: RewriteFlags,
+}
+
+impl RewriteRule {
+  /// Constructs a new RewriteRule from the given pattern, substitution, and
+  /// flags.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::{RewriteFlags, RewriteRule};
+  /// let rule = RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlags::default())
+  ///   .expect("should be valid regex");
+  /// ```
+  pub fn new<S>(pattern: &str, substitution: S, flags: RewriteFlags) -> Result<Box<Self>, Error>
+  where
+    S: Into<String>,
+  {
+    let pattern = RegexBuilder::new(pattern)
+      .case_insensitive(flags.case_insensitive)
+      .build()?;
+
+    Ok(Box::new(Self {
+      pattern,
+      substitution: substitution.into(),
+      flags,
+    }))
+  }
+
+  /// Evaluates this rule against `request`, applying its substitution and
+  /// flag semantics. Returns [`RewriteOutcome::Unmatched`] if the pattern
+  /// doesn't match the request path.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::{Request, rewrite::{RewriteFlags, RewriteRule, RewriteOutcome}};
+  /// # let docroot = std::env::temp_dir();
+  /// let rule = RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlags::default())
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/old/page")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rule.apply(&request, &docroot) {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/new/page");
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  pub fn apply(&self, request: &Request, _docroot: &Path) -> RewriteOutcome {
+    let input = request.url().path();
+
+    if !self.pattern.is_match(input) {
+      return RewriteOutcome::Unmatched;
+    }
+
+    let output = self
+      .pattern
+      .replace(input, self.substitution.clone())
+      .into_owned();
+
+    if self.flags.forbidden {
+      return RewriteOutcome::Forbidden;
+    }
+
+    if let Some(status) = self.flags.redirect {
+      return RewriteOutcome::Redirect {
+        status,
+        location: output,
+      };
+    }
+
+    let mut url = request.url().clone();
+    url.set_path(&output);
+
+    if self.flags.query_string_append {
+      if let Some(query) = request.url().query() {
+        url.set_query(Some(query));
+      }
+    }
+
+    let rewritten = request
+      .extend()
+      .url(url)
+      .expect("re-serialized url should re-parse")
+      .build()
+      .unwrap_or_else(|_| request.clone());
+
+    RewriteOutcome::Matched {
+      request: rewritten,
+      last: self.flags.last,
+    }
+  }
+}
+
+impl Rewriter for RewriteRule {
+  /// Applies this rule's substitution and full `L`/`R`/`F` flag semantics,
+  /// via [`RewriteRule::apply`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::{Request, rewrite::{Rewriter, RewriteFlags, RewriteOutcome, RewriteRule}};
+  /// # let docroot = std::env::temp_dir();
+  /// let rule = RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlags::default())
+  ///   .expect("should be valid regex");
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/old/page")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rule.rewrite(request, &docroot).expect("should rewrite") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/new/page");
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    Ok(self.apply(&request, docroot))
+  }
+}
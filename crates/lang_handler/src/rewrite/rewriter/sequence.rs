@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use super::{Request, RequestBuilderException, Rewriter};
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
 
 // Tested via Rewriter::then(...) doc-test
 
@@ -40,7 +40,12 @@ where
   A: Rewriter + ?Sized,
   B: Rewriter + ?Sized,
 {
-  /// Rewrite a request using the first rewriter, then the second.
+  /// Rewrite a request using the first rewriter, then the second - unless
+  /// the first rewriter's outcome is terminal (`Redirect`, `Forbidden`,
+  /// `Respond`, or `Matched` with `last: true`), in which case the second
+  /// rewriter is skipped and that outcome is returned directly. This
+  /// mirrors Apache's `[L]` flag: a matched "last" rule stops the rest of
+  /// the chain from running.
   ///
   /// # Examples
   ///
@@ -48,7 +53,7 @@ where
   /// # use std::path::Path;
   /// # use lang_handler::{
   /// #   Request,
-  /// #   rewrite::{Rewriter, RewriterSequence, PathRewriter}
+  /// #   rewrite::{Rewriter, RewriterSequence, RewriteOutcome, PathRewriter}
   /// # };
   /// # let docroot = std::env::temp_dir();
   /// let first = PathRewriter::new("^(.*)$", "/bar$1")
@@ -64,13 +69,26 @@ where
   ///   .build()
   ///   .expect("should build request");
   ///
-  /// let new_request = sequence.rewrite(request, &docroot)
-  ///   .expect("should rewrite request");
-  ///
-  /// assert_eq!(new_request.url().path(), "/foo/bar/index.php".to_string());
+  /// match sequence.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/foo/bar/index.php".to_string());
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
   /// ```
-  fn rewrite(&self, request: Request, docroot: &Path) -> Result<Request, RequestBuilderException> {
-    let request = self.0.rewrite(request, docroot)?;
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    let outcome = self.0.rewrite(request.clone(), docroot)?;
+
+    let (request, last) = match outcome {
+      RewriteOutcome::Unmatched => (request, false),
+      RewriteOutcome::Matched { request, last } => (request, last),
+      terminal => return Ok(terminal),
+    };
+
+    if last {
+      return Ok(RewriteOutcome::Matched { request, last: true });
+    }
+
     self.1.rewrite(request, docroot)
   }
 }
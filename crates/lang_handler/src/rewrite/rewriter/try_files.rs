@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+use super::{Request, RequestBuilderException, RewriteOutcome, Rewriter};
+use crate::rewrite::{Captures, MatchContext};
+
+/// Expands `$uri` to the request path, then `%1`..`%9` backreferences from
+/// `captures` the same way [`super::PathRewriter`] expands them, in a
+/// `try_files`-style candidate or fallback template such as `$uri.php` or
+/// `/cache/%1.html`. A missing group, or no captures at all, expands to an
+/// empty string - the same convention `PathRewriter` uses.
+pub(crate) fn expand_candidate(template: &str, uri: &str, captures: Option<&Captures>) -> String {
+  let template = template.replace("$uri", uri);
+  let mut output = String::with_capacity(template.len());
+  let mut chars = template.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    if c != '%' {
+      output.push(c);
+      continue;
+    }
+
+    match chars.peek() {
+      Some('%') => {
+        chars.next();
+        output.push('%');
+      }
+      Some(d) if d.is_ascii_digit() && *d != '0' => {
+        let index = d.to_digit(10).expect("digit") as usize;
+        chars.next();
+        if let Some(value) = captures.and_then(|captures| captures.get(index)) {
+          output.push_str(value);
+        }
+      }
+      _ => output.push('%'),
+    }
+  }
+
+  output
+}
+
+/// Resolves an already-expanded `candidate` path against `docroot`,
+/// returning the canonicalized path if it exists as a regular file and
+/// stays within `docroot` - rejecting `..` escapes the same way
+/// [`translate_path`](crate) style docroot confinement does elsewhere in
+/// this crate's consumers. Returns `None` for a directory, a missing path,
+/// or an escape.
+pub(crate) fn resolve_candidate(docroot: &Path, candidate: &str) -> Option<PathBuf> {
+  let relative = candidate.strip_prefix('/').unwrap_or(candidate);
+  let resolved = docroot.join(relative).canonicalize().ok()?;
+
+  if !resolved.starts_with(docroot) || !resolved.is_file() {
+    return None;
+  }
+
+  Some(resolved)
+}
+
+/// An nginx `try_files` / actix-files default-service style fallback:
+/// tries each of `candidates` in order, rewriting the request to the first
+/// one that resolves to an existing file under the docroot, or to
+/// `fallback` (e.g. a PHP front controller script) if none do. The
+/// original request query string is always preserved on the rewritten
+/// request so the eventual target can still read it.
+///
+/// Candidate and fallback templates may reference `$uri` (the request
+/// path) and `%1`..`%9` backreferences from an enclosing
+/// [`Condition`](crate::rewrite::Condition)'s captures, the same way
+/// [`super::PathRewriter`] expands backreferences via
+/// [`Rewriter::rewrite_with`].
+///
+/// # Examples
+///
+/// ```
+/// # use lang_handler::{
+/// #   rewrite::{Rewriter, RewriteOutcome, TryFilesRewriter},
+/// #   Request,
+/// #   MockRoot,
+/// # };
+/// # let docroot = MockRoot::builder()
+/// #   .file("index.php", "<?php echo \"Hello, world!\"; ?>")
+/// #   .build()
+/// #   .expect("should prepare docroot");
+/// let rewriter = TryFilesRewriter::new(
+///   vec!["$uri".to_string(), "$uri/index.html".to_string()],
+///   "/index.php".to_string(),
+/// );
+///
+/// let request = Request::builder()
+///   .url("http://example.com/missing?x=1")
+///   .build()
+///   .expect("should build request");
+///
+/// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+///   RewriteOutcome::Matched { request, .. } => {
+///     assert_eq!(request.url().path(), "/index.php");
+///     assert_eq!(request.url().query(), Some("x=1"));
+///   }
+///   other => panic!("expected a match, got {:?}", other),
+/// }
+/// ```
+pub struct TryFilesRewriter {
+  candidates: Vec<String>,
+  fallback: String,
+}
+
+impl TryFilesRewriter {
+  /// Construct a new TryFilesRewriter trying each of `candidates` in order,
+  /// falling back to `fallback` if none resolve to an existing file.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::rewrite::TryFilesRewriter;
+  /// let rewriter = TryFilesRewriter::new(vec!["$uri".to_string()], "/index.php".to_string());
+  /// ```
+  pub fn new(candidates: Vec<String>, fallback: String) -> Box<Self> {
+    Box::new(Self { candidates, fallback })
+  }
+}
+
+impl Rewriter for TryFilesRewriter {
+  /// Rewrite the request to the first existing candidate, or to `fallback`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use lang_handler::{
+  /// #   rewrite::{Rewriter, RewriteOutcome, TryFilesRewriter},
+  /// #   Request,
+  /// #   MockRoot,
+  /// # };
+  /// # let docroot = MockRoot::builder()
+  /// #   .file("exists.php", "<?php echo \"Hello, world!\"; ?>")
+  /// #   .build()
+  /// #   .expect("should prepare docroot");
+  /// let rewriter = TryFilesRewriter::new(vec!["$uri".to_string()], "/index.php".to_string());
+  ///
+  /// let request = Request::builder()
+  ///   .url("http://example.com/exists.php")
+  ///   .build()
+  ///   .expect("should build request");
+  ///
+  /// match rewriter.rewrite(request, &docroot).expect("should rewrite request") {
+  ///   RewriteOutcome::Matched { request, .. } => {
+  ///     assert_eq!(request.url().path(), "/exists.php");
+  ///   }
+  ///   other => panic!("expected a match, got {:?}", other),
+  /// }
+  /// ```
+  fn rewrite(&self, request: Request, docroot: &Path) -> Result<RewriteOutcome, RequestBuilderException> {
+    self.apply(request, docroot, None)
+  }
+
+  /// Rewrite the request as in [`rewrite`](Rewriter::rewrite), additionally
+  /// expanding `%1`..`%9` backreferences from the [`MatchContext`]'s
+  /// captures in each candidate and the fallback.
+  fn rewrite_with(
+    &self,
+    request: Request,
+    docroot: &Path,
+    context: &MatchContext,
+  ) -> Result<RewriteOutcome, RequestBuilderException> {
+    self.apply(request, docroot, context.captures())
+  }
+}
+
+impl TryFilesRewriter {
+  fn apply(
+    &self,
+    request: Request,
+    docroot: &Path,
+    captures: Option<&Captures>,
+  ) -> Result<RewriteOutcome, RequestBuilderException> {
+    let uri = request.url().path().to_string();
+
+    let target = self
+      .candidates
+      .iter()
+      .map(|candidate| expand_candidate(candidate, &uri, captures))
+      .find(|expanded| resolve_candidate(docroot, expanded).is_some())
+      .unwrap_or_else(|| expand_candidate(&self.fallback, &uri, captures));
+
+    let (path, query) = match target.find('?') {
+      Some(index) => (&target[..index], Some(&target[index + 1..])),
+      None => (target.as_str(), None),
+    };
+
+    // A template carrying its own `?query` gets the original query string
+    // appended to it rather than replacing it.
+    let original = request.url().query().filter(|query| !query.is_empty());
+    let merged = match (query, original) {
+      (Some(rewritten), Some(original)) => Some(format!("{rewritten}&{original}")),
+      (rewritten, original) => rewritten.or(original).map(str::to_string),
+    };
+
+    let mut url = request.url().clone();
+    url.set_path(path);
+    url.set_query(merged.as_deref());
+
+    let request = request
+      .extend()
+      .url(url)
+      .expect("re-serialized url should re-parse")
+      .build()?;
+
+    Ok(RewriteOutcome::Matched { request, last: false })
+  }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+
+/// An HTTP status code paired with its standard reason phrase.
+///
+/// # Example
+///
+/// ```
+/// use lang_handler::Status;
+///
+/// let status = Status::new(404);
+/// assert_eq!(status.code(), 404);
+/// assert_eq!(status.reason(), "Not Found");
+/// assert!(status.is_client_error());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status(i32);
+
+impl Status {
+  /// `200 OK`
+  pub const OK: Status = Status(200);
+  /// `201 Created`
+  pub const CREATED: Status = Status(201);
+  /// `204 No Content`
+  pub const NO_CONTENT: Status = Status(204);
+  /// `301 Moved Permanently`
+  pub const MOVED_PERMANENTLY: Status = Status(301);
+  /// `302 Found`
+  pub const FOUND: Status = Status(302);
+  /// `304 Not Modified`
+  pub const NOT_MODIFIED: Status = Status(304);
+  /// `400 Bad Request`
+  pub const BAD_REQUEST: Status = Status(400);
+  /// `401 Unauthorized`
+  pub const UNAUTHORIZED: Status = Status(401);
+  /// `403 Forbidden`
+  pub const FORBIDDEN: Status = Status(403);
+  /// `404 Not Found`
+  pub const NOT_FOUND: Status = Status(404);
+  /// `405 Method Not Allowed`
+  pub const METHOD_NOT_ALLOWED: Status = Status(405);
+  /// `500 Internal Server Error`
+  pub const INTERNAL_SERVER_ERROR: Status = Status(500);
+  /// `502 Bad Gateway`
+  pub const BAD_GATEWAY: Status = Status(502);
+  /// `503 Service Unavailable`
+  pub const SERVICE_UNAVAILABLE: Status = Status(503);
+  /// `504 Gateway Timeout`
+  pub const GATEWAY_TIMEOUT: Status = Status(504);
+
+  /// Creates a `Status` from a raw numeric code. Accepts any `i32`, even
+  /// ones outside the standard 100-599 range or without a known reason
+  /// phrase, so a [`ResponseBuilder`](super::ResponseBuilder) can still
+  /// carry an application-defined status through.
+  pub fn new(code: i32) -> Self {
+    Self(code)
+  }
+
+  /// Returns the numeric status code.
+  pub fn code(&self) -> i32 {
+    self.0
+  }
+
+  /// Returns the standard reason phrase for this status code, or `""` if
+  /// the code isn't a recognized standard one.
+  pub fn reason(&self) -> &'static str {
+    match self.0 {
+      100 => "Continue",
+      101 => "Switching Protocols",
+      200 => "OK",
+      201 => "Created",
+      202 => "Accepted",
+      204 => "No Content",
+      206 => "Partial Content",
+      301 => "Moved Permanently",
+      302 => "Found",
+      303 => "See Other",
+      304 => "Not Modified",
+      307 => "Temporary Redirect",
+      308 => "Permanent Redirect",
+      400 => "Bad Request",
+      401 => "Unauthorized",
+      403 => "Forbidden",
+      404 => "Not Found",
+      405 => "Method Not Allowed",
+      406 => "Not Acceptable",
+      408 => "Request Timeout",
+      409 => "Conflict",
+      410 => "Gone",
+      411 => "Length Required",
+      412 => "Precondition Failed",
+      413 => "Payload Too Large",
+      414 => "URI Too Long",
+      415 => "Unsupported Media Type",
+      422 => "Unprocessable Entity",
+      429 => "Too Many Requests",
+      500 => "Internal Server Error",
+      501 => "Not Implemented",
+      502 => "Bad Gateway",
+      503 => "Service Unavailable",
+      504 => "Gateway Timeout",
+      _ => "",
+    }
+  }
+
+  /// Returns `true` for the informational class (100-199).
+  pub fn is_informational(&self) -> bool {
+    (100..200).contains(&self.0)
+  }
+
+  /// Returns `true` for the success class (200-299).
+  pub fn is_success(&self) -> bool {
+    (200..300).contains(&self.0)
+  }
+
+  /// Returns `true` for the redirection class (300-399).
+  pub fn is_redirection(&self) -> bool {
+    (300..400).contains(&self.0)
+  }
+
+  /// Returns `true` for the client error class (400-499).
+  pub fn is_client_error(&self) -> bool {
+    (400..500).contains(&self.0)
+  }
+
+  /// Returns `true` for the server error class (500-599).
+  pub fn is_server_error(&self) -> bool {
+    (500..600).contains(&self.0)
+  }
+}
+
+impl Default for Status {
+  /// Defaults to `200 OK`, matching [`ResponseBuilder`](super::ResponseBuilder)'s
+  /// default status.
+  fn default() -> Self {
+    Status::OK
+  }
+}
+
+impl From<i32> for Status {
+  fn from(code: i32) -> Self {
+    Status::new(code)
+  }
+}
+
+impl From<Status> for i32 {
+  fn from(status: Status) -> Self {
+    status.0
+  }
+}
+
+impl fmt::Display for Status {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {}", self.0, self.reason())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn reports_known_reason_phrases() {
+    assert_eq!(Status::new(200).reason(), "OK");
+    assert_eq!(Status::new(404).reason(), "Not Found");
+    assert_eq!(Status::new(500).reason(), "Internal Server Error");
+  }
+
+  #[test]
+  fn reports_empty_reason_for_unknown_codes() {
+    assert_eq!(Status::new(599).reason(), "");
+  }
+
+  #[test]
+  fn classifies_status_codes() {
+    assert!(Status::new(100).is_informational());
+    assert!(Status::new(200).is_success());
+    assert!(Status::new(301).is_redirection());
+    assert!(Status::new(404).is_client_error());
+    assert!(Status::new(500).is_server_error());
+  }
+
+  #[test]
+  fn displays_as_code_and_reason() {
+    assert_eq!(Status::new(404).to_string(), "404 Not Found");
+  }
+}
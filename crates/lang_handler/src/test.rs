@@ -7,6 +7,8 @@ use std::{
   path::{Path, PathBuf},
 };
 
+use crate::{percent_encode, EncodeSet, Handler, Request, RequestBuilder};
+
 /// A mock document root for testing purposes.
 pub struct MockRoot(PathBuf);
 
@@ -20,8 +22,8 @@ impl MockRoot {
   /// # use lang_handler::MockRoot;
   /// # let docroot = std::env::temp_dir().join("test");
   /// let files = HashMap::from([
-  ///   (PathBuf::new().join("file1.txt"), "Hello, world!".to_string()),
-  ///   (PathBuf::new().join("file2.txt"), "Goodbye, world!".to_string())
+  ///   (PathBuf::new().join("file1.txt"), b"Hello, world!".to_vec()),
+  ///   (PathBuf::new().join("file2.txt"), b"Goodbye, world!".to_vec())
   /// ]);
   ///
   /// let mock_root = MockRoot::new(&docroot, files)
@@ -30,12 +32,12 @@ impl MockRoot {
   pub fn new<D, H>(docroot: D, files: H) -> Result<Self, Error>
   where
     D: AsRef<Path>,
-    H: Into<HashMap<PathBuf, String>>,
+    H: Into<HashMap<PathBuf, Vec<u8>>>,
   {
     let docroot = docroot.as_ref();
     create_dir_all(docroot)?;
 
-    let map: HashMap<PathBuf, String> = files.into();
+    let map: HashMap<PathBuf, Vec<u8>> = files.into();
     for (path, contents) in map.iter() {
       let stripped = path.strip_prefix("/").unwrap_or(path);
 
@@ -45,7 +47,7 @@ impl MockRoot {
       }
 
       let mut file = File::create(file_path)?;
-      file.write_all(contents.as_bytes())?;
+      file.write_all(contents)?;
     }
 
     // This unwrap should be safe due to creating the docroot base dir above.
@@ -96,8 +98,13 @@ impl DerefMut for MockRoot {
 }
 
 /// A builder for creating a MockRoot with specified files.
+///
+/// Files are staged as raw bytes under the hood, so [`file_bytes`](Self::file_bytes)
+/// and [`file`](Self::file) (a `&str`/`String` convenience over it) can be
+/// freely mixed - a fixture can stage a binary asset like an image
+/// alongside ordinary text files.
 #[derive(Debug)]
-pub struct MockRootBuilder(PathBuf, HashMap<PathBuf, String>);
+pub struct MockRootBuilder(PathBuf, HashMap<PathBuf, Vec<u8>>);
 
 impl MockRootBuilder {
   /// Create a new MockRootBuilder with the specified document root.
@@ -116,7 +123,7 @@ impl MockRootBuilder {
     Self(docroot.as_ref().to_owned(), HashMap::new())
   }
 
-  /// Add a file to the MockRootBuilder.
+  /// Add a text file to the MockRootBuilder.
   ///
   /// # Examples
   ///
@@ -126,10 +133,30 @@ impl MockRootBuilder {
   /// let builder = MockRootBuilder::new(&docroot)
   ///   .file("bar.txt", "Hello, world!");
   /// ```
-  pub fn file<P, C>(mut self, path: P, contents: C) -> MockRootBuilder
+  pub fn file<P, C>(self, path: P, contents: C) -> MockRootBuilder
   where
     P: AsRef<Path>,
     C: Into<String>,
+  {
+    self.file_bytes(path, contents.into().into_bytes())
+  }
+
+  /// Add a binary file to the MockRootBuilder, for fixtures like images or
+  /// other non-UTF-8 assets that [`file`](Self::file)'s `String` contents
+  /// can't represent.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// # use lang_handler::MockRootBuilder;
+  /// # let docroot = std::env::temp_dir().join("test");
+  /// let builder = MockRootBuilder::new(&docroot)
+  ///   .file_bytes("favicon.ico", &[0u8, 1, 2, 3]);
+  /// ```
+  pub fn file_bytes<P, C>(mut self, path: P, contents: C) -> MockRootBuilder
+  where
+    P: AsRef<Path>,
+    C: Into<Vec<u8>>,
   {
     let path = path.as_ref().to_owned();
     let contents = contents.into();
@@ -160,3 +187,208 @@ impl Default for MockRootBuilder {
     Self::new(temp_dir().join("php-temp-dir-base"))
   }
 }
+
+/// The base URL `TestRequest`'s method constructors resolve a path against,
+/// so a test can write `TestRequest::get("/foo")` without spelling out a
+/// scheme and host it doesn't care about.
+const TEST_REQUEST_BASE_URL: &str = "http://example.test";
+
+/// A fluent builder for constructing a representative [`Request`] in tests,
+/// without assembling a [`Request::builder()`] chain by hand each time.
+/// Pairs with [`MockRoot`] - stage a docroot with one, build a request with
+/// the other - and with [`TestRequest::run`] to dispatch it through a
+/// [`Handler`] in one call.
+///
+/// # Examples
+///
+/// ```
+/// use lang_handler::TestRequest;
+///
+/// let request = TestRequest::get("/users/42")
+///   .header("Accept", "application/json")
+///   .build();
+///
+/// assert_eq!(request.url().path(), "/users/42");
+/// assert_eq!(request.headers().get("Accept"), Some("application/json".to_string()));
+/// ```
+pub struct TestRequest(RequestBuilder);
+
+impl TestRequest {
+  fn with_method_and_path(method: &str, path: &str) -> Self {
+    let builder = Request::builder()
+      .method(method)
+      .url(format!("{TEST_REQUEST_BASE_URL}{path}"))
+      .expect("TestRequest path should produce a valid url");
+
+    Self(builder)
+  }
+
+  /// Constructs a `GET` request for `path`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::get("/index.php").build();
+  /// assert_eq!(request.method(), "GET");
+  /// ```
+  pub fn get<P: AsRef<str>>(path: P) -> Self {
+    Self::with_method_and_path("GET", path.as_ref())
+  }
+
+  /// Constructs a `POST` request for `path`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::post("/submit").build();
+  /// assert_eq!(request.method(), "POST");
+  /// ```
+  pub fn post<P: AsRef<str>>(path: P) -> Self {
+    Self::with_method_and_path("POST", path.as_ref())
+  }
+
+  /// Constructs a `PUT` request for `path`.
+  pub fn put<P: AsRef<str>>(path: P) -> Self {
+    Self::with_method_and_path("PUT", path.as_ref())
+  }
+
+  /// Constructs a `PATCH` request for `path`.
+  pub fn patch<P: AsRef<str>>(path: P) -> Self {
+    Self::with_method_and_path("PATCH", path.as_ref())
+  }
+
+  /// Constructs a `DELETE` request for `path`.
+  pub fn delete<P: AsRef<str>>(path: P) -> Self {
+    Self::with_method_and_path("DELETE", path.as_ref())
+  }
+
+  /// Sets a header on the request being built.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::get("/").header("X-Test", "1").build();
+  /// assert_eq!(request.headers().get("X-Test"), Some("1".to_string()));
+  /// ```
+  pub fn header<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: Into<String>,
+    V: Into<String>,
+  {
+    self.0 = self.0.header(key, value);
+    self
+  }
+
+  /// Sets the body to `pairs` urlencoded as `application/x-www-form-urlencoded`,
+  /// and sets the `Content-Type` header to match, the same as an HTML form
+  /// submission.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::post("/login")
+  ///   .form([("username", "admin"), ("password", "hunter2")])
+  ///   .build();
+  ///
+  /// assert_eq!(
+  ///   request.headers().get("Content-Type"),
+  ///   Some("application/x-www-form-urlencoded".to_string())
+  /// );
+  /// assert_eq!(request.body(), "username=admin&password=hunter2");
+  /// ```
+  pub fn form<I, K, V>(mut self, pairs: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let body = pairs
+      .into_iter()
+      .map(|(key, value)| {
+        format!(
+          "{}={}",
+          percent_encode(key.as_ref(), EncodeSet::Component),
+          percent_encode(value.as_ref(), EncodeSet::Component)
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("&");
+
+    self.0 = self
+      .0
+      .header("Content-Type", "application/x-www-form-urlencoded")
+      .body(body);
+
+    self
+  }
+
+  /// Sets the body to `body` - already-serialized JSON, since this crate
+  /// has no JSON serializer to hand - and sets the `Content-Type` header
+  /// to `application/json`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::post("/users")
+  ///   .json(r#"{"name":"PHP"}"#)
+  ///   .build();
+  ///
+  /// assert_eq!(request.headers().get("Content-Type"), Some("application/json".to_string()));
+  /// assert_eq!(request.body(), r#"{"name":"PHP"}"#);
+  /// ```
+  pub fn json<T: Into<String>>(mut self, body: T) -> Self {
+    self.0 = self.0.header("Content-Type", "application/json").body(body.into());
+    self
+  }
+
+  /// Builds the `Request`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::TestRequest;
+  ///
+  /// let request = TestRequest::get("/").build();
+  /// assert_eq!(request.url().path(), "/");
+  /// ```
+  pub fn build(self) -> Request {
+    self.0.build().expect("TestRequest should always build a valid request")
+  }
+
+  /// Builds the `Request` and dispatches it through `handler` in one call.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::{Handler, Request, Response, ResponseBuilder, TestRequest};
+  ///
+  /// struct Echo;
+  /// impl Handler for Echo {
+  ///   type Error = String;
+  ///
+  ///   fn handle(&self, request: Request) -> Result<Response, Self::Error> {
+  ///     Ok(Response::builder().status(200).body(request.body()).build())
+  ///   }
+  /// }
+  ///
+  /// let response = TestRequest::post("/echo")
+  ///   .json(r#"{"ok":true}"#)
+  ///   .run(&Echo)
+  ///   .expect("should handle request");
+  ///
+  /// assert_eq!(response.body(), r#"{"ok":true}"#);
+  /// ```
+  pub fn run<H: Handler>(self, handler: &H) -> Result<crate::Response, H::Error> {
+    handler.handle(self.build())
+  }
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// The HTTP protocol version of a request, following `http-types`'
+/// `Version` enum.
+///
+/// # Examples
+///
+/// ```
+/// use lang_handler::Version;
+///
+/// assert_eq!(Version::Http1_1.to_string(), "HTTP/1.1");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Version {
+  /// HTTP/0.9
+  Http0_9,
+  /// HTTP/1.0
+  Http1_0,
+  /// HTTP/1.1
+  Http1_1,
+  /// HTTP/2.0
+  Http2_0,
+  /// HTTP/3.0
+  Http3_0,
+}
+
+impl Version {
+  /// Returns the `SERVER_PROTOCOL`-style string for this Version, e.g.
+  /// `"HTTP/1.1"`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lang_handler::Version;
+  ///
+  /// assert_eq!(Version::Http2_0.as_str(), "HTTP/2.0");
+  /// ```
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Version::Http0_9 => "HTTP/0.9",
+      Version::Http1_0 => "HTTP/1.0",
+      Version::Http1_1 => "HTTP/1.1",
+      Version::Http2_0 => "HTTP/2.0",
+      Version::Http3_0 => "HTTP/3.0",
+    }
+  }
+}
+
+impl fmt::Display for Version {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
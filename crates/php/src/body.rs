@@ -0,0 +1,100 @@
+use std::fmt;
+
+use bytes::{Bytes, BytesMut};
+
+/// A pluggable source of request body data.
+///
+/// `sapi_module_read_post` pulls from this on demand rather than assuming
+/// the whole body has already been materialized into memory, so large
+/// `multipart/form-data` uploads can be processed with bounded memory when
+/// backed by a streaming source instead of [`BufferedBody`].
+pub trait RequestBody: fmt::Debug + Send {
+  /// Returns up to `max` bytes of body data, or `None` at true EOF.
+  ///
+  /// Implementations may block the calling thread until the next chunk is
+  /// available, but must not return `Some(Bytes::new())` to signal EOF —
+  /// use `None` for that.
+  fn read_chunk(&mut self, max: usize) -> Option<Bytes>;
+}
+
+/// A [`RequestBody`] backed by a fully materialized, in-memory buffer.
+///
+/// This is the common case: request bodies that have already been read off
+/// the wire in their entirety before being handed to PHP.
+#[derive(Debug, Default)]
+pub struct BufferedBody(BytesMut);
+
+impl BufferedBody {
+  /// Wraps an existing buffer as a [`RequestBody`].
+  pub fn new(buffer: BytesMut) -> Self {
+    Self(buffer)
+  }
+}
+
+impl RequestBody for BufferedBody {
+  fn read_chunk(&mut self, max: usize) -> Option<Bytes> {
+    if self.0.is_empty() {
+      return None;
+    }
+
+    let take = max.min(self.0.len());
+    Some(self.0.split_to(take).freeze())
+  }
+}
+
+/// A [`RequestBody`] fed incrementally, one frame at a time, mirroring how
+/// chunked transfer-encoding decoding hands buffers up as they arrive. This
+/// lets the request body be streamed into PHP without buffering the entire
+/// upload in memory first.
+pub struct StreamedBody {
+  chunks: std::sync::mpsc::Receiver<Bytes>,
+  leftover: Option<Bytes>,
+  closed: bool,
+}
+
+impl fmt::Debug for StreamedBody {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("StreamedBody")
+      .field("leftover", &self.leftover)
+      .field("closed", &self.closed)
+      .finish()
+  }
+}
+
+impl StreamedBody {
+  /// Creates a new streamed body fed by `chunks`. The producer side should
+  /// drop its `Sender` once the upload is fully received so `read_chunk`
+  /// can report EOF.
+  pub fn new(chunks: std::sync::mpsc::Receiver<Bytes>) -> Self {
+    Self {
+      chunks,
+      leftover: None,
+      closed: false,
+    }
+  }
+}
+
+impl RequestBody for StreamedBody {
+  fn read_chunk(&mut self, max: usize) -> Option<Bytes> {
+    if self.closed {
+      return None;
+    }
+
+    let mut chunk = match self.leftover.take() {
+      Some(chunk) => chunk,
+      None => match self.chunks.recv() {
+        Ok(chunk) => chunk,
+        Err(_) => {
+          self.closed = true;
+          return None;
+        }
+      },
+    };
+
+    if chunk.len() > max {
+      self.leftover = Some(chunk.split_off(max));
+    }
+
+    Some(chunk)
+  }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use http_handler::{Request, Response};
+
+use super::{embed::RequestRewriter, EmbedRequestError};
+
+/// A fallback action to take when a request fails with a status registered
+/// in a [`Catchers`] registry, instead of letting the error propagate to the
+/// caller.
+pub enum Catcher {
+  /// Respond with a fixed `Response`, ignoring the error that triggered it.
+  Response(Response),
+
+  /// Re-dispatch the original request through `rewriter` and retry once,
+  /// enabling the classic front-controller pattern where any missing file
+  /// rewrites to `index.php`.
+  ///
+  /// The retry dispatches a clone of the request taken before the first
+  /// attempt, so it carries the original headers, method, URL, and buffered
+  /// body. A body passed separately to
+  /// [`Embed::handle_streamed`](crate::Embed::handle_streamed) is not
+  /// replayed: the retry reads the request's own buffered body instead, so
+  /// `php://input` in a catcher-triggered retry of a streamed request does
+  /// not see the streamed bytes.
+  Rewriter(Box<dyn RequestRewriter>),
+
+  /// Calls `handler` with the request that failed and the triggering
+  /// error's display text, producing a fresh `Response` - e.g. a branded
+  /// error page or a JSON error envelope that embeds details a fixed
+  /// [`Response`](Catcher::Response) can't.
+  Handler(Box<dyn Fn(&Request, &str) -> Response + Send + Sync>),
+}
+
+impl std::fmt::Debug for Catcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Catcher::Response(response) => f.debug_tuple("Response").field(response).finish(),
+      Catcher::Rewriter(_) => f
+        .debug_tuple("Rewriter")
+        .field(&"Box<dyn RequestRewriter>")
+        .finish(),
+      Catcher::Handler(_) => f
+        .debug_tuple("Handler")
+        .field(&"Box<dyn Fn(&Request, &str) -> Response>")
+        .finish(),
+    }
+  }
+}
+
+/// Maps a request-lifecycle error to the HTTP status code it would
+/// conventionally produce, for [`Catchers`] lookup.
+pub(crate) fn status_for_error(error: &EmbedRequestError) -> u16 {
+  match error {
+    EmbedRequestError::ScriptNotFound(_) => 404,
+    EmbedRequestError::ExpectedAbsoluteRequestUri(_) => 400,
+    EmbedRequestError::InvalidRequestUri(_) => 400,
+    EmbedRequestError::PathTraversal(_) => 404,
+    EmbedRequestError::DirectoryIndexNotFound(_) => 403,
+    EmbedRequestError::Timeout => 504,
+    EmbedRequestError::PermissionDenied(_) => 403,
+    _ => 500,
+  }
+}
+
+/// A registry of [`Catcher`]s keyed by HTTP status code, consulted by
+/// [`Embed`](super::Embed) when a request fails, before the error
+/// propagates to the caller.
+///
+/// Defaults to empty, which disables this behavior entirely so existing
+/// error handling is unchanged.
+#[derive(Default)]
+pub struct Catchers {
+  by_status: HashMap<u16, Catcher>,
+  default: Option<Box<Catcher>>,
+}
+
+impl Catchers {
+  /// Creates an empty catcher registry.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers `catcher` to run when a request fails with `status`,
+  /// replacing any catcher already registered for that status.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use php::{Catcher, Catchers};
+  ///
+  /// let response = http_handler::response::Response::builder()
+  ///   .status(404)
+  ///   .body(bytes::BytesMut::from("Not Found"))
+  ///   .expect("should build response");
+  ///
+  /// let catchers = Catchers::new()
+  ///   .register(404, Catcher::Response(response));
+  /// ```
+  pub fn register(mut self, status: u16, catcher: Catcher) -> Self {
+    self.by_status.insert(status, catcher);
+    self
+  }
+
+  /// Registers `catcher` as the catch-all run for any failing status that
+  /// doesn't have a more specific catcher registered via
+  /// [`register`](Self::register), replacing any catch-all already set.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use php::{Catcher, Catchers};
+  ///
+  /// let response = http_handler::response::Response::builder()
+  ///   .status(500)
+  ///   .body(bytes::BytesMut::from("Something went wrong"))
+  ///   .expect("should build response");
+  ///
+  /// let catchers = Catchers::new()
+  ///   .register_default(Catcher::Response(response));
+  /// ```
+  pub fn register_default(mut self, catcher: Catcher) -> Self {
+    self.default = Some(Box::new(catcher));
+    self
+  }
+
+  /// Looks up the catcher registered for `status`, falling back to the
+  /// catch-all registered via [`register_default`](Self::register_default)
+  /// if no catcher is registered for `status` specifically.
+  pub(crate) fn get(&self, status: u16) -> Option<&Catcher> {
+    self.by_status.get(&status).or(self.default.as_deref())
+  }
+}
+
+impl std::fmt::Debug for Catchers {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Catchers")
+      .field("registered", &self.by_status.keys().collect::<Vec<_>>())
+      .field("has_default", &self.default.is_some())
+      .finish()
+  }
+}
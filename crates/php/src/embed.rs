@@ -3,12 +3,13 @@ use std::{
   ops::DerefMut,
   path::{Path, PathBuf},
   sync::Arc,
+  time::{Duration, SystemTime},
 };
 
 use ext_php_rs::{
   alloc::{efree, estrdup},
   error::Error,
-  ffi::{php_execute_script, sapi_get_default_content_type},
+  ffi::{php_execute_script, sapi_get_default_content_type, zend_set_timeout},
   zend::{try_catch, try_catch_first, ExecutorGlobals, SapiGlobals},
 };
 
@@ -16,6 +17,13 @@ use http_handler::{Handler, Request, Response};
 use http_rewriter::RewriteError;
 
 use super::{
+  auth::Authorization,
+  body::{BufferedBody, RequestBody},
+  catcher::{status_for_error, Catcher, Catchers},
+  http_date::{format_http_date, parse_http_date},
+  mime::mime_type_for_extension,
+  permissions::Permissions,
+  proxy::TrustedProxies,
   sapi::{ensure_sapi, Sapi},
   scopes::{FileHandleScope, RequestScope},
   strings::translate_path,
@@ -38,6 +46,18 @@ pub struct Embed {
   sapi: Arc<Sapi>,
 
   rewriter: Option<Box<dyn RequestRewriter>>,
+
+  trusted_proxies: TrustedProxies,
+
+  serve_static: bool,
+
+  catchers: Catchers,
+
+  directory_index: Vec<String>,
+
+  timeout: Option<Duration>,
+
+  permissions: Option<Permissions>,
 }
 
 impl std::fmt::Debug for Embed {
@@ -47,6 +67,12 @@ impl std::fmt::Debug for Embed {
       .field("args", &self.args)
       .field("sapi", &self.sapi)
       .field("rewriter", &"Box<dyn RequestRewriter>")
+      .field("trusted_proxies", &self.trusted_proxies)
+      .field("serve_static", &self.serve_static)
+      .field("catchers", &self.catchers)
+      .field("directory_index", &self.directory_index)
+      .field("timeout", &self.timeout)
+      .field("permissions", &self.permissions)
       .finish()
   }
 }
@@ -135,9 +161,190 @@ impl Embed {
       args: argv.iter().map(|v| v.as_ref().to_string()).collect(),
       sapi: ensure_sapi()?,
       rewriter,
+      trusted_proxies: TrustedProxies::default(),
+      serve_static: false,
+      catchers: Catchers::default(),
+      directory_index: vec!["index.php".to_string()],
+      timeout: None,
+      permissions: None,
     })
   }
 
+  /// Configures the trusted-proxy policy used to resolve `REMOTE_ADDR`,
+  /// `REQUEST_SCHEME`, `SERVER_NAME`, and `SERVER_PORT` from `X-Forwarded-*`
+  /// headers when the immediate peer is a trusted proxy.
+  ///
+  /// Defaults to an empty policy, which disables forwarded header
+  /// resolution entirely so direct-connection behavior is unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::env::current_dir;
+  /// use php::{Embed, IpCidr, TrustedProxies};
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_trusted_proxies(TrustedProxies::new(vec![
+  ///     IpCidr::parse("10.0.0.0/8").expect("should be valid CIDR"),
+  ///   ]));
+  /// ```
+  pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+    self.trusted_proxies = trusted_proxies;
+    self
+  }
+
+  /// Enables serving non-`.php` files under the docroot directly, without
+  /// going through the SAPI. Serves `Content-Type`, `Content-Length`,
+  /// `Last-Modified`, and a weak `ETag`, and honors `If-None-Match` /
+  /// `If-Modified-Since` with `304 Not Modified` - bypassing
+  /// `php_execute_script` entirely for a path [`serve_static_file`](Self::serve_static_file)
+  /// (or the directory-index fallback in [`respond_with_static_file`](Self::respond_with_static_file))
+  /// resolves to an existing non-`.php` file, rather than just reusing the
+  /// SAPI's normal response path for a file PHP itself would've read anyway.
+  ///
+  /// Defaults to `false`, which always routes requests through PHP, so
+  /// existing router-style front controllers that want every request
+  /// (including asset requests) to reach `index.php` are unaffected.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::env::current_dir;
+  /// use php::Embed;
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_serve_static(true);
+  /// ```
+  pub fn with_serve_static(mut self, serve_static: bool) -> Self {
+    self.serve_static = serve_static;
+    self
+  }
+
+  /// Configures the ordered list of directory-index candidates tried when a
+  /// request's path ends in `/`, à la a web server's `DirectoryIndex`
+  /// directive - e.g. `["index.php", "index.html"]` tries `index.php`
+  /// first, falling back to `index.html` if it doesn't exist.
+  ///
+  /// Defaults to `["index.php"]`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::env::current_dir;
+  /// use php::Embed;
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_directory_index(vec!["index.php".to_string(), "index.html".to_string()]);
+  /// ```
+  pub fn with_directory_index(mut self, directory_index: Vec<String>) -> Self {
+    self.directory_index = directory_index;
+    self
+  }
+
+  /// Configures fallback [`Catcher`]s to run, keyed by HTTP status code,
+  /// when a request fails before its error propagates to the caller — e.g.
+  /// mapping a 404 to a static error page, or rewriting it to `index.php`
+  /// for a front-controller pattern.
+  ///
+  /// Defaults to an empty registry, which disables this behavior entirely
+  /// so existing error handling is unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::env::current_dir;
+  /// use php::{Catcher, Catchers, Embed};
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let response = http_handler::response::Response::builder()
+  ///   .status(404)
+  ///   .body(bytes::BytesMut::from("Not Found"))
+  ///   .expect("should build response");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_catchers(Catchers::new().register(404, Catcher::Response(response)));
+  /// ```
+  pub fn with_catchers(mut self, catchers: Catchers) -> Self {
+    self.catchers = catchers;
+    self
+  }
+
+  /// Bounds how long a single request's `php_execute_script` call may run
+  /// before it's aborted, à la actix-web's slow-request timeout. Armed via
+  /// PHP's own `zend_set_timeout`, so the script is interrupted the same way
+  /// it would be by `max_execution_time` expiring, and the bailout this
+  /// produces is reported as [`EmbedRequestError::Timeout`] rather than the
+  /// generic [`EmbedRequestError::Bailout`], so callers can distinguish a
+  /// hang from a crash.
+  ///
+  /// Defaults to `None`, which leaves scripts to run unbounded.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::{env::current_dir, time::Duration};
+  /// use php::Embed;
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_timeout(Duration::from_secs(30));
+  /// ```
+  pub fn with_timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Attaches a [`Permissions`] capability policy, following Deno's
+  /// permission-container model. Its environment-variable rules constrain
+  /// which process environment variables are copied into a script's
+  /// `$_SERVER` (`getenv()` is not filtered), and its filesystem rules
+  /// constrain which static assets [`with_serve_static`](Self::with_serve_static)
+  /// will read from disk on the script's behalf, with a violation returning
+  /// [`PermissionDenied`](EmbedRequestError::PermissionDenied).
+  /// Its network rules, and filesystem access a PHP script makes itself via
+  /// `fopen()`/`include()`, are not yet enforced; see [`Permissions`]'s
+  /// documentation for the full scope.
+  ///
+  /// Defaults to `None`, which leaves scripts with today's unrestricted
+  /// environment visibility and `with_serve_static` confined to `docroot`
+  /// only.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::env::current_dir;
+  /// use php::{Embed, Permissions};
+  ///
+  /// let docroot = current_dir()
+  ///   .expect("should have current_dir");
+  ///
+  /// let embed = Embed::new(docroot, None)
+  ///   .expect("should construct Embed")
+  ///   .with_permissions(Permissions::builder().env_var("API_KEY").build());
+  /// ```
+  pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+    self.permissions = Some(permissions);
+    self
+  }
+
   /// Get the docroot used for this Embed instance
   ///
   /// # Examples
@@ -157,6 +364,104 @@ impl Embed {
   pub fn docroot(&self) -> &Path {
     self.docroot.as_path()
   }
+
+  /// Serves `request_path` directly from the docroot if it resolves to an
+  /// existing non-`.php` file, handling conditional GET via `ETag`/
+  /// `Last-Modified`. `If-None-Match` takes precedence over
+  /// `If-Modified-Since` when both are present on the request. Returns
+  /// `Ok(None)` (falling through to PHP handling) if the path doesn't exist
+  /// or is a `.php` script, and [`PermissionDenied`](EmbedRequestError::PermissionDenied)
+  /// if it resolves outside both the docroot and any filesystem root
+  /// allowlisted by the configured [`Permissions`].
+  fn serve_static_file(
+    &self,
+    request_path: &str,
+    headers: &http_handler::HeaderMap,
+  ) -> Result<Option<Response>, EmbedRequestError> {
+    let Some(relative) = Path::new(request_path).strip_prefix("/").ok() else {
+      return Ok(None);
+    };
+
+    let Some(candidate) = self.docroot.join(relative).canonicalize().ok() else {
+      return Ok(None);
+    };
+
+    let permissions = self.permissions.clone().unwrap_or_default();
+    if !permissions.allows_path(&self.docroot, &candidate) {
+      return Err(EmbedRequestError::PermissionDenied(format!(
+        "{} is outside the docroot and any allowlisted filesystem root",
+        candidate.display()
+      )));
+    }
+
+    Ok(self.respond_with_static_file(&candidate, headers))
+  }
+
+  /// Serves `candidate` directly from disk, handling conditional GET via
+  /// `ETag`/`Last-Modified` as [`serve_static_file`](Self::serve_static_file)
+  /// does. Unlike `serve_static_file`, `candidate` is assumed to already be
+  /// resolved (e.g. by [`translate_path`] picking a directory-index file) -
+  /// this only re-checks that it's an existing non-`.php` file before
+  /// serving it.
+  fn respond_with_static_file(
+    &self,
+    candidate: &Path,
+    headers: &http_handler::HeaderMap,
+  ) -> Option<Response> {
+    if !candidate.is_file() {
+      return None;
+    }
+
+    let extension = candidate
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .unwrap_or("");
+
+    if extension.eq_ignore_ascii_case("php") {
+      return None;
+    }
+
+    let metadata = std::fs::metadata(&candidate).ok()?;
+    let modified = metadata.modified().ok()?;
+    let mtime_secs = modified.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
+    let etag = format!("W/\"{:x}-{:x}\"", mtime_secs, metadata.len());
+    let last_modified = format_http_date(modified);
+
+    let if_none_match = headers.get("If-None-Match").and_then(|v| v.to_str().ok());
+    let if_modified_since = headers.get("If-Modified-Since").and_then(|v| v.to_str().ok());
+
+    // If-None-Match takes precedence over If-Modified-Since when both are
+    // present, per RFC 7232 §3.3 - a stale cache validator shouldn't be
+    // allowed to mask a fresher ETag mismatch.
+    let not_modified = match if_none_match {
+      Some(etag_header) => etag_header == etag,
+      None => if_modified_since
+        .and_then(parse_http_date)
+        .map(|since| mtime_secs as i64 <= since)
+        .unwrap_or(false),
+    };
+
+    if not_modified {
+      return http_handler::response::Response::builder()
+        .status(304)
+        .header("ETag", etag)
+        .header("Last-Modified", last_modified)
+        .body(bytes::BytesMut::new())
+        .ok();
+    }
+
+    let body = std::fs::read(&candidate).ok()?;
+    let content_type = mime_type_for_extension(extension);
+
+    http_handler::response::Response::builder()
+      .status(200)
+      .header("Content-Type", content_type)
+      .header("Content-Length", body.len().to_string())
+      .header("Last-Modified", last_modified)
+      .header("ETag", etag)
+      .body(bytes::BytesMut::from(body.as_slice()))
+      .ok()
+  }
 }
 
 #[async_trait::async_trait]
@@ -195,6 +500,54 @@ impl Handler for Embed {
   /// //assert_eq!(response.body(), "Hello, world!");
   /// ```
   async fn handle(&self, request: Request) -> Result<Response, Self::Error> {
+    self.dispatch(request, None).await
+  }
+}
+
+impl Embed {
+  /// Handles an HTTP request the same way [`Handler::handle`] does, except
+  /// `php://input` is read from `body` instead of the request's own
+  /// (already fully materialized) body. This lets a caller stream a large
+  /// request body into PHP incrementally — e.g. backed by a
+  /// [`crate::StreamedBody`] fed as chunks arrive off the wire —
+  /// rather than buffering the entire upload in memory first.
+  pub async fn handle_streamed(
+    &self,
+    request: Request,
+    body: Box<dyn RequestBody>,
+  ) -> Result<Response, EmbedRequestError> {
+    self.dispatch(request, Some(body)).await
+  }
+
+  async fn dispatch(
+    &self,
+    request: Request,
+    body_override: Option<Box<dyn RequestBody>>,
+  ) -> Result<Response, EmbedRequestError> {
+    let retry_request = request.clone();
+
+    match self.dispatch_once(request, body_override).await {
+      Ok(response) => Ok(response),
+      Err(error) => match self.catchers.get(status_for_error(&error)) {
+        Some(Catcher::Response(response)) => Ok(response.clone()),
+        Some(Catcher::Rewriter(rewriter)) => {
+          let rewritten = rewriter
+            .rewrite_request(retry_request)
+            .map_err(|e| EmbedRequestError::RequestRewriteError(e.to_string()))?;
+
+          self.dispatch_once(rewritten, None).await
+        }
+        Some(Catcher::Handler(handler)) => Ok(handler(&retry_request, &error.to_string())),
+        None => Err(error),
+      },
+    }
+  }
+
+  async fn dispatch_once(
+    &self,
+    request: Request,
+    body_override: Option<Box<dyn RequestBody>>,
+  ) -> Result<Response, EmbedRequestError> {
     let docroot = self.docroot.clone();
 
     // Initialize the SAPI module
@@ -212,9 +565,26 @@ impl Handler for Embed {
         .map_err(|e| EmbedRequestError::RequestRewriteError(e.to_string()))?;
     }
 
-    let translated_path = translate_path(&docroot, request.uri().path())?
-      .display()
-      .to_string();
+    if self.serve_static {
+      if let Some(response) = self.serve_static_file(request.uri().path(), request.headers())? {
+        return Ok(response);
+      }
+    }
+
+    let translated_path_buf = translate_path(&docroot, request.uri().path(), &self.directory_index)?;
+
+    // A directory-index candidate (e.g. `index.html`) may have resolved to a
+    // non-PHP file, in which case it should be served the same way a direct
+    // request for that file would be.
+    if self.serve_static {
+      if let Some(response) =
+        self.respond_with_static_file(&translated_path_buf, request.headers())
+      {
+        return Ok(response);
+      }
+    }
+
+    let translated_path = translated_path_buf.display().to_string();
 
     // Convert REQUEST_URI and PATH_TRANSLATED to C strings
     let request_uri = estrdup(request_uri);
@@ -236,6 +606,23 @@ impl Handler for Embed {
       .and_then(|s| s.parse::<i64>().ok())
       .unwrap_or(0);
 
+    // Decode Basic credentials / stash the raw Digest challenge so PHP sees
+    // PHP_AUTH_USER, PHP_AUTH_PW, and PHP_AUTH_DIGEST.
+    let authorization = headers
+      .get("Authorization")
+      .and_then(|v| v.to_str().ok())
+      .and_then(Authorization::parse);
+
+    let (auth_user, auth_password, auth_digest) = match &authorization {
+      Some(Authorization::Basic { user, password }) => {
+        (estrdup(user.clone()), estrdup(password.clone()), std::ptr::null_mut())
+      }
+      Some(Authorization::Digest(challenge)) => {
+        (std::ptr::null_mut(), std::ptr::null_mut(), estrdup(challenge.clone()))
+      }
+      None => (std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut()),
+    };
+
     // Prepare argv and argc
     let argc = self.args.len() as i32;
     let mut argv_ptrs = vec![];
@@ -244,10 +631,19 @@ impl Handler for Embed {
     }
 
     let script_name = translated_path.clone();
+    let timeout = self.timeout;
 
     // Fixed RefUnwindSafe issue (FIXME.md #1) by setting up RequestContext before try_catch_first
     // This avoids the need to rebuild the request inside the closure
-    RequestContext::for_request(request, docroot.clone());
+    let (request_parts, request_body) = request.into_parts();
+    let request_body = body_override.unwrap_or_else(|| Box::new(BufferedBody::new(request_body)));
+    RequestContext::for_parts(
+      request_parts,
+      request_body,
+      docroot.clone(),
+      self.trusted_proxies.clone(),
+      self.permissions.clone().unwrap_or_default(),
+    );
 
     let response = try_catch_first(move || {
 
@@ -269,7 +665,9 @@ impl Handler for Embed {
         globals.request_info.path_translated = path_translated;
         globals.request_info.request_uri = request_uri;
 
-        // TODO: Add auth fields
+        globals.request_info.auth_user = auth_user;
+        globals.request_info.auth_password = auth_password;
+        globals.request_info.auth_digest = auth_digest;
 
         globals.request_info.content_type = content_type;
         globals.request_info.content_length = content_length;
@@ -280,8 +678,31 @@ impl Handler for Embed {
       // Run script in its own try/catch so bailout doesn't skip request shutdown.
       {
         let mut file_handle = FileHandleScope::new(script_name.clone());
-        try_catch(|| unsafe { php_execute_script(file_handle.deref_mut()) })
-          .map_err(|_| EmbedRequestError::Bailout)?;
+
+        // Arms PHP's own execution-time watchdog, the same mechanism behind
+        // `max_execution_time`, so a runaway script bails out on its own
+        // rather than tying up this worker forever. `zend_set_timeout` only
+        // takes whole seconds, and `0` means "no timeout" in its watchdog
+        // semantics, so round any sub-second duration up to 1 rather than
+        // truncating it away to nothing.
+        if let Some(timeout) = timeout {
+          let seconds = timeout.as_secs().max(1);
+          unsafe { zend_set_timeout(seconds as _, false) };
+        }
+
+        if try_catch(|| unsafe { php_execute_script(file_handle.deref_mut()) }).is_err() {
+          // `timed_out` is only set when the watchdog above fired; any other
+          // bailout (e.g. a fatal error or `exit()`) leaves it unset, so this
+          // distinguishes a hang from a crash rather than collapsing both
+          // into the generic `Bailout`.
+          let timed_out = ExecutorGlobals::get().timed_out;
+
+          return Err(if timed_out {
+            EmbedRequestError::Timeout
+          } else {
+            EmbedRequestError::Bailout
+          });
+        }
       }
 
       if let Some(err) = ExecutorGlobals::take_exception() {
@@ -328,11 +749,19 @@ impl Handler for Embed {
         ctx.add_response_header("Content-Type", mime);
       }
 
-      // Build the final response with accumulated data using the extension system
-      RequestContext::reclaim()
-        .ok_or(EmbedRequestError::ResponseBuildError)?
+      // Build the final response with accumulated data using the extension system,
+      // then return the context's allocation to the thread-local pool instead of
+      // dropping it, so the next request on this thread can reuse it.
+      let mut context =
+        RequestContext::reclaim().ok_or(EmbedRequestError::ResponseBuildError)?;
+
+      let response = context
         .build_response()
-        .map_err(|_| EmbedRequestError::ResponseBuildError)
+        .map_err(|_| EmbedRequestError::ResponseBuildError);
+
+      RequestContext::release(context);
+
+      response
     })
     .unwrap_or(Err(EmbedRequestError::Bailout))?;
 
@@ -64,6 +64,18 @@ pub enum EmbedRequestError {
   /// Script not found in the document root
   ScriptNotFound(String),
 
+  /// The request URI contained a malformed `%XX` escape, or percent-decoded
+  /// to a byte sequence that isn't valid UTF-8 or includes a NUL byte
+  InvalidRequestUri(String),
+
+  /// The translated path resolved outside of the document root, e.g. via a
+  /// `..` segment or a symlink escape
+  PathTraversal(String),
+
+  /// A directory was requested but none of the configured directory-index
+  /// candidates (e.g. `index.php`, `index.html`) exist within it
+  DirectoryIndexNotFound(String),
+
   /// Failed to determine the content type of the response
   FailedToDetermineContentType,
 
@@ -75,6 +87,14 @@ pub enum EmbedRequestError {
 
   /// Error during request rewriting
   RequestRewriteError(RequestBuilderException),
+
+  /// The request exceeded its configured execution timeout
+  Timeout,
+
+  /// The request was denied by the configured [`Permissions`](crate::Permissions)
+  /// policy, e.g. a static asset outside of `docroot` and any allowlisted
+  /// filesystem root.
+  PermissionDenied(String),
 }
 
 impl std::fmt::Display for EmbedRequestError {
@@ -98,6 +118,11 @@ impl std::fmt::Display for EmbedRequestError {
         write!(f, "Expected absolute REQUEST_URI: {}", e)
       }
       EmbedRequestError::ScriptNotFound(e) => write!(f, "Script not found: {}", e),
+      EmbedRequestError::InvalidRequestUri(e) => write!(f, "Invalid request URI: {}", e),
+      EmbedRequestError::PathTraversal(e) => write!(f, "Path traversal rejected: {}", e),
+      EmbedRequestError::DirectoryIndexNotFound(e) => {
+        write!(f, "No directory index found: {}", e)
+      }
       EmbedRequestError::FailedToDetermineContentType => {
         write!(f, "Failed to determine content type")
       }
@@ -110,6 +135,8 @@ impl std::fmt::Display for EmbedRequestError {
       EmbedRequestError::RequestRewriteError(e) => {
         write!(f, "Request rewrite error: {}", e)
       }
+      EmbedRequestError::Timeout => write!(f, "Request exceeded its execution timeout"),
+      EmbedRequestError::PermissionDenied(e) => write!(f, "Permission denied: {}", e),
     }
   }
 }
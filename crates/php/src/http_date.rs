@@ -0,0 +1,105 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+  "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// Howard Hinnant's "days_from_civil"/"civil_from_days" algorithms, which
+// convert between a civil (year, month, day) triple and a day count
+// relative to the Unix epoch using only integer arithmetic.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let y = if month <= 2 { year - 1 } else { year };
+  let era = (if y >= 0 { y } else { y - 399 }) / 400;
+  let yoe = y - era * 400;
+  let mp = (month + 9) % 12;
+  let doy = (153 * mp + 2) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+  let z = days + 719468;
+  let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
+  let doe = z - era * 146097;
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+  let y = yoe + era * 400;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let d = doy - (153 * mp + 2) / 5 + 1;
+  let m = if mp < 10 { mp + 3 } else { mp - 9 };
+  let y = if m <= 2 { y + 1 } else { y };
+  (y, m as u32, d as u32)
+}
+
+/// Formats a [`SystemTime`] as an RFC 7231 IMF-fixdate
+/// (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`), the form used by `Last-Modified`
+/// and `Date` headers.
+pub(crate) fn format_http_date(time: SystemTime) -> String {
+  let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+  let days = secs.div_euclid(86400);
+  let time_of_day = secs.rem_euclid(86400);
+
+  let (year, month, day) = civil_from_days(days);
+  let weekday = (days.rem_euclid(7) + 4) % 7;
+
+  let hour = time_of_day / 3600;
+  let minute = (time_of_day % 3600) / 60;
+  let second = time_of_day % 60;
+
+  format!(
+    "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+    DAYS[weekday as usize],
+    day,
+    MONTHS[(month - 1) as usize],
+    year,
+    hour,
+    minute,
+    second
+  )
+}
+
+/// Parses an RFC 7231 IMF-fixdate string back into seconds since the Unix
+/// epoch. Only the canonical IMF-fixdate form emitted by
+/// [`format_http_date`] is supported; the obsolete RFC 850 and asctime
+/// formats are not recognized.
+pub(crate) fn parse_http_date(input: &str) -> Option<i64> {
+  let (_weekday, rest) = input.trim().split_once(", ")?;
+
+  let mut parts = rest.split_whitespace();
+  let day: i64 = parts.next()?.parse().ok()?;
+  let month = parts.next()?;
+  let year: i64 = parts.next()?.parse().ok()?;
+  let time = parts.next()?;
+  if parts.next() != Some("GMT") {
+    return None;
+  }
+
+  let month = MONTHS.iter().position(|&m| m == month)? as i64 + 1;
+
+  let mut time_parts = time.split(':');
+  let hour: i64 = time_parts.next()?.parse().ok()?;
+  let minute: i64 = time_parts.next()?.parse().ok()?;
+  let second: i64 = time_parts.next()?.parse().ok()?;
+
+  let days = days_from_civil(year, month, day);
+  Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn formats_known_date() {
+    let time = UNIX_EPOCH + std::time::Duration::from_secs(784111777);
+    assert_eq!(format_http_date(time), "Sun, 06 Nov 1994 08:49:37 GMT");
+  }
+
+  #[test]
+  fn round_trips_through_parse() {
+    let time = UNIX_EPOCH + std::time::Duration::from_secs(784111777);
+    let formatted = format_http_date(time);
+    assert_eq!(parse_http_date(&formatted), Some(784111777));
+  }
+}
@@ -48,14 +48,22 @@
 #![warn(clippy::dbg_macro, clippy::print_stdout)]
 #![warn(missing_docs)]
 
+mod auth;
+mod body;
+mod catcher;
 mod embed;
 mod exception;
+mod http_date;
+mod mime;
+mod permissions;
+mod proxy;
 mod request_context;
 mod rewriter_impl;
 mod sapi;
 mod scopes;
 mod strings;
 mod test;
+mod trace;
 
 pub use http_handler::{
     Handler, Request, Response,
@@ -69,7 +77,11 @@ pub use http_handler::{
     header::HeaderName as Header,
 };
 
+pub use body::{BufferedBody, RequestBody, StreamedBody};
+pub use catcher::{Catcher, Catchers};
 pub use embed::{Embed, RequestRewriter};
+pub use permissions::{NetworkRule, Permissions, PermissionsBuilder};
+pub use proxy::{IpCidr, IpCidrError, TrustedProxies};
 pub use exception::{EmbedRequestError, EmbedStartError};
 pub use request_context::RequestContext;
 pub use rewriter_impl::*;
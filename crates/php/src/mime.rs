@@ -0,0 +1,27 @@
+/// Returns a best-effort MIME type for a file extension (without the
+/// leading dot), used when serving static assets directly instead of
+/// going through PHP. Falls back to `application/octet-stream` for
+/// anything unrecognized.
+pub(crate) fn mime_type_for_extension(extension: &str) -> &'static str {
+  match extension.to_ascii_lowercase().as_str() {
+    "html" | "htm" => "text/html; charset=utf-8",
+    "css" => "text/css; charset=utf-8",
+    "js" | "mjs" => "application/javascript; charset=utf-8",
+    "json" => "application/json",
+    "txt" => "text/plain; charset=utf-8",
+    "xml" => "application/xml",
+    "svg" => "image/svg+xml",
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "ico" => "image/x-icon",
+    "woff" => "font/woff",
+    "woff2" => "font/woff2",
+    "ttf" => "font/ttf",
+    "otf" => "font/otf",
+    "wasm" => "application/wasm",
+    "pdf" => "application/pdf",
+    _ => "application/octet-stream",
+  }
+}
@@ -0,0 +1,234 @@
+//! A capability policy for scripts executed through [`Embed`](crate::Embed),
+//! following Deno's permission-container model: filesystem roots beyond
+//! `docroot`, outbound network hosts/ports, and visible environment
+//! variables are all denied unless explicitly allowlisted.
+//!
+//! **This is not yet a full sandbox.** The environment-variable and
+//! `Embed`-served-static-asset axes are enforced; a PHP script's own
+//! `fopen()`/`fsockopen()`/`curl` calls are not (see `Permissions`'
+//! "Enforcement scope" section below) - do not rely on this to isolate a
+//! running script's own filesystem or network access until that's wired up
+//! to a real SAPI-level enforcement point.
+
+use std::path::{Path, PathBuf};
+
+/// A single outbound network rule, allowing connections to a host and
+/// optionally restricting them to one port.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkRule {
+  host: String,
+  port: Option<u16>,
+}
+
+impl NetworkRule {
+  /// Allows connections to any port on `host`.
+  pub fn host<H: Into<String>>(host: H) -> Self {
+    Self {
+      host: host.into(),
+      port: None,
+    }
+  }
+
+  /// Allows connections to `host` only on `port`.
+  pub fn host_port<H: Into<String>>(host: H, port: u16) -> Self {
+    Self {
+      host: host.into(),
+      port: Some(port),
+    }
+  }
+
+  fn allows(&self, host: &str, port: u16) -> bool {
+    self.host.eq_ignore_ascii_case(host) && self.port.is_none_or_eq(port)
+  }
+}
+
+trait PortMatch {
+  fn is_none_or_eq(&self, port: u16) -> bool;
+}
+
+impl PortMatch for Option<u16> {
+  fn is_none_or_eq(&self, port: u16) -> bool {
+    match self {
+      Some(allowed) => *allowed == port,
+      None => true,
+    }
+  }
+}
+
+/// A capability policy describing what a script executed through
+/// [`Embed`](crate::Embed) may touch: filesystem roots beyond `docroot`,
+/// outbound network host/port rules, and which process environment
+/// variables are copied into the script's `$_SERVER`.
+///
+/// Unlike most configuration on [`Embed`](crate::Embed), which defaults to
+/// whatever preserves today's unrestricted behavior, `Permissions` is
+/// deny-by-default once attached via
+/// [`with_permissions`](crate::Embed::with_permissions) - a sandbox that
+/// defaults open isn't a sandbox. [`Embed::with_permissions`](crate::Embed::with_permissions)
+/// itself still defaults to `None`, so existing callers see no behavior
+/// change until they opt in.
+///
+/// # Enforcement scope
+///
+/// The process environment variables copied into the script's `$_SERVER`
+/// are checked against this policy with [`allows_env_var`](Self::allows_env_var);
+/// a variable it does not allow is left out, and the request goes on.
+/// Every static asset [`with_serve_static`](crate::Embed::with_serve_static)
+/// reads directly from disk on the script's behalf is checked with
+/// [`allows_path`](Self::allows_path), denying the request with
+/// [`PermissionDenied`](crate::EmbedRequestError::PermissionDenied)
+/// (HTTP 403) on a violation. `getenv()` is not filtered: it reads the host
+/// process environment directly.
+///
+/// [`allows_network`](Self::allows_network) is **not** enforced anywhere
+/// yet: `Embed` itself never opens outbound sockets, that happens entirely
+/// inside the PHP engine via `fsockopen()`/`curl`, which isn't checked
+/// against `network` rules. The same is true of a PHP script's own
+/// `fopen()`/`include()` calls - only `Embed`'s own direct disk reads (the
+/// translated script path, static assets) are confined to `docroot` and
+/// checked against `filesystem_root`. Enforcing either rule against PHP
+/// userland itself would require installing a PHP stream wrapper or an
+/// `open_basedir`-style hook at the SAPI level, which isn't wired up yet -
+/// until it is, a running script can read or connect to anything the host
+/// process can, regardless of what's configured here.
+///
+/// # Examples
+///
+/// ```
+/// use php::{NetworkRule, Permissions};
+///
+/// let permissions = Permissions::builder()
+///   .filesystem_root("/srv/shared-assets")
+///   .network(NetworkRule::host_port("api.example.com", 443))
+///   .env_var("API_KEY")
+///   .build();
+///
+/// assert!(permissions.allows_network("api.example.com", 443));
+/// assert!(!permissions.allows_network("api.example.com", 80));
+/// assert!(permissions.allows_env_var("API_KEY"));
+/// assert!(!permissions.allows_env_var("HOME"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Permissions {
+  filesystem_roots: Vec<PathBuf>,
+  network_rules: Vec<NetworkRule>,
+  env_vars: Vec<String>,
+}
+
+impl Permissions {
+  /// Creates a fully restrictive `Permissions` with no filesystem roots
+  /// beyond `docroot`, no outbound network access, and no visible
+  /// environment variables.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns a builder for constructing a `Permissions`.
+  pub fn builder() -> PermissionsBuilder {
+    PermissionsBuilder::default()
+  }
+
+  /// Returns whether `path` falls under `docroot` or one of the configured
+  /// filesystem roots.
+  pub fn allows_path<P: AsRef<Path>>(&self, docroot: &Path, path: P) -> bool {
+    let path = path.as_ref();
+    path.starts_with(docroot) || self.filesystem_roots.iter().any(|root| path.starts_with(root))
+  }
+
+  /// Returns whether a connection to `host`:`port` is allowed.
+  pub fn allows_network(&self, host: &str, port: u16) -> bool {
+    self.network_rules.iter().any(|rule| rule.allows(host, port))
+  }
+
+  /// Returns whether `name` is visible to the script's environment.
+  pub fn allows_env_var(&self, name: &str) -> bool {
+    self.env_vars.iter().any(|allowed| allowed == name)
+  }
+}
+
+/// Builder for [`Permissions`].
+#[derive(Default)]
+pub struct PermissionsBuilder {
+  filesystem_roots: Vec<PathBuf>,
+  network_rules: Vec<NetworkRule>,
+  env_vars: Vec<String>,
+}
+
+impl PermissionsBuilder {
+  /// Allows filesystem access under `root`, in addition to `docroot`.
+  pub fn filesystem_root<P: AsRef<Path>>(mut self, root: P) -> Self {
+    self.filesystem_roots.push(root.as_ref().to_owned());
+    self
+  }
+
+  /// Allows an outbound network connection matching `rule`.
+  pub fn network(mut self, rule: NetworkRule) -> Self {
+    self.network_rules.push(rule);
+    self
+  }
+
+  /// Allows the process environment variable `name` to be copied into the
+  /// script's `$_SERVER`.
+  pub fn env_var<S: Into<String>>(mut self, name: S) -> Self {
+    self.env_vars.push(name.into());
+    self
+  }
+
+  /// Builds the `Permissions`.
+  pub fn build(self) -> Permissions {
+    Permissions {
+      filesystem_roots: self.filesystem_roots,
+      network_rules: self.network_rules,
+      env_vars: self.env_vars,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn allows_paths_under_docroot_without_configuration() {
+    let permissions = Permissions::new();
+    assert!(permissions.allows_path(Path::new("/srv/docroot"), "/srv/docroot/index.php"));
+    assert!(!permissions.allows_path(Path::new("/srv/docroot"), "/etc/passwd"));
+  }
+
+  #[test]
+  fn allows_paths_under_configured_filesystem_roots() {
+    let permissions = Permissions::builder()
+      .filesystem_root("/srv/shared-assets")
+      .build();
+
+    assert!(permissions.allows_path(Path::new("/srv/docroot"), "/srv/shared-assets/logo.png"));
+    assert!(!permissions.allows_path(Path::new("/srv/docroot"), "/srv/other/logo.png"));
+  }
+
+  #[test]
+  fn matches_network_rules_by_host_and_optional_port() {
+    let permissions = Permissions::builder()
+      .network(NetworkRule::host("anyport.example.com"))
+      .network(NetworkRule::host_port("api.example.com", 443))
+      .build();
+
+    assert!(permissions.allows_network("anyport.example.com", 8080));
+    assert!(permissions.allows_network("api.example.com", 443));
+    assert!(!permissions.allows_network("api.example.com", 80));
+    assert!(!permissions.allows_network("unknown.example.com", 443));
+  }
+
+  #[test]
+  fn denies_network_and_env_vars_by_default() {
+    let permissions = Permissions::new();
+    assert!(!permissions.allows_network("api.example.com", 443));
+    assert!(!permissions.allows_env_var("HOME"));
+  }
+
+  #[test]
+  fn allows_only_configured_env_vars() {
+    let permissions = Permissions::builder().env_var("API_KEY").build();
+    assert!(permissions.allows_env_var("API_KEY"));
+    assert!(!permissions.allows_env_var("API_SECRET"));
+  }
+}
@@ -0,0 +1,215 @@
+//! A trusted-proxy policy for resolving client-facing request state
+//! (`REMOTE_ADDR`, scheme, host, port) from `X-Forwarded-*` headers.
+
+use std::net::IpAddr;
+
+/// A single CIDR block used to decide whether a peer address is a trusted
+/// proxy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IpCidr {
+  addr: IpAddr,
+  prefix_len: u8,
+}
+
+/// Error produced when parsing an invalid CIDR notation string.
+#[derive(Debug, PartialEq, Eq)]
+pub enum IpCidrError {
+  /// The string wasn't of the form `address/prefix_len`.
+  MalformedCidr(String),
+
+  /// The address portion could not be parsed as an IP address.
+  InvalidAddress(String),
+
+  /// The prefix length portion was out of range for the address family.
+  InvalidPrefixLength(String),
+}
+
+impl std::fmt::Display for IpCidrError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      IpCidrError::MalformedCidr(s) => write!(f, "Malformed CIDR notation: \"{}\"", s),
+      IpCidrError::InvalidAddress(s) => write!(f, "Invalid IP address: \"{}\"", s),
+      IpCidrError::InvalidPrefixLength(s) => write!(f, "Invalid prefix length: \"{}\"", s),
+    }
+  }
+}
+
+impl IpCidr {
+  /// Parses a CIDR notation string, e.g. `10.0.0.0/8` or `::1/128`.
+  pub fn parse(cidr: &str) -> Result<Self, IpCidrError> {
+    let (addr, prefix_len) = cidr
+      .split_once('/')
+      .ok_or_else(|| IpCidrError::MalformedCidr(cidr.to_string()))?;
+
+    let parsed_addr: IpAddr = addr
+      .parse()
+      .map_err(|_| IpCidrError::InvalidAddress(addr.to_string()))?;
+
+    let max_prefix = match parsed_addr {
+      IpAddr::V4(_) => 32,
+      IpAddr::V6(_) => 128,
+    };
+
+    let parsed_prefix_len: u8 = prefix_len
+      .parse()
+      .ok()
+      .filter(|len| *len <= max_prefix)
+      .ok_or_else(|| IpCidrError::InvalidPrefixLength(prefix_len.to_string()))?;
+
+    Ok(Self {
+      addr: parsed_addr,
+      prefix_len: parsed_prefix_len,
+    })
+  }
+
+  /// Returns whether the given address falls within this CIDR block.
+  fn contains(&self, addr: IpAddr) -> bool {
+    match (self.addr, addr) {
+      (IpAddr::V4(network), IpAddr::V4(candidate)) => {
+        let mask = mask_u32(self.prefix_len);
+        u32::from(network) & mask == u32::from(candidate) & mask
+      }
+      (IpAddr::V6(network), IpAddr::V6(candidate)) => {
+        let mask = mask_u128(self.prefix_len);
+        u128::from(network) & mask == u128::from(candidate) & mask
+      }
+      _ => false,
+    }
+  }
+}
+
+fn mask_u32(prefix_len: u8) -> u32 {
+  if prefix_len == 0 {
+    0
+  } else {
+    u32::MAX << (32 - prefix_len)
+  }
+}
+
+fn mask_u128(prefix_len: u8) -> u128 {
+  if prefix_len == 0 {
+    0
+  } else {
+    u128::MAX << (128 - prefix_len)
+  }
+}
+
+/// Client-facing request state resolved from `X-Forwarded-*` headers, once
+/// the immediate peer has been confirmed as a trusted proxy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForwardedInfo {
+  /// The resolved client address, read from `X-Forwarded-For`.
+  pub remote_addr: Option<IpAddr>,
+
+  /// The resolved scheme, from `X-Forwarded-Proto`.
+  pub scheme: Option<String>,
+
+  /// The resolved host, from `X-Forwarded-Host`.
+  pub host: Option<String>,
+
+  /// The resolved port, from `X-Forwarded-Port`.
+  pub port: Option<u16>,
+}
+
+/// A policy describing which immediate peer addresses are trusted to supply
+/// `X-Forwarded-*` headers describing the true client.
+///
+/// Defaults to an empty list of trusted proxies, which disables forwarded
+/// header resolution entirely so direct-connection behavior is unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies(Vec<IpCidr>);
+
+impl TrustedProxies {
+  /// Constructs a trusted-proxy policy from a list of CIDR blocks.
+  pub fn new(cidrs: Vec<IpCidr>) -> Self {
+    Self(cidrs)
+  }
+
+  /// Returns whether the given peer address is a trusted proxy.
+  pub fn trusts(&self, addr: IpAddr) -> bool {
+    self.0.iter().any(|cidr| cidr.contains(addr))
+  }
+
+  /// Resolves client-facing request state from `X-Forwarded-*` headers, if
+  /// `peer` is a trusted proxy. Returns a default (all-`None`) [`ForwardedInfo`]
+  /// if `peer` is untrusted, absent, or no trusted proxies are configured.
+  ///
+  /// `X-Forwarded-For` is read right-to-left (nearest hop first, since each
+  /// proxy appends its own view of the peer to the end of the list),
+  /// stopping at and returning the first untrusted address encountered —
+  /// everything to the right of it is our own trusted infrastructure.
+  pub fn resolve(
+    &self,
+    peer: Option<IpAddr>,
+    forwarded_for: Option<&str>,
+    forwarded_proto: Option<&str>,
+    forwarded_host: Option<&str>,
+    forwarded_port: Option<&str>,
+  ) -> ForwardedInfo {
+    match peer {
+      Some(peer) if self.trusts(peer) => {}
+      _ => return ForwardedInfo::default(),
+    }
+
+    let remote_addr = forwarded_for.and_then(|chain| {
+      chain
+        .split(',')
+        .map(str::trim)
+        .filter(|hop| !hop.is_empty())
+        .rev()
+        .find_map(|hop| {
+          let addr: IpAddr = hop.parse().ok()?;
+          if self.trusts(addr) {
+            None
+          } else {
+            Some(addr)
+          }
+        })
+    });
+
+    ForwardedInfo {
+      remote_addr,
+      scheme: forwarded_proto.map(str::to_owned),
+      host: forwarded_host.map(str::to_owned),
+      port: forwarded_port.and_then(|port| port.parse().ok()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn resolves_through_trusted_hops_only() {
+    let proxies = TrustedProxies::new(vec![
+      IpCidr::parse("10.0.0.0/8").expect("should parse"),
+    ]);
+
+    let peer: IpAddr = "10.0.0.1".parse().unwrap();
+    let info = proxies.resolve(
+      Some(peer),
+      Some("203.0.113.5, 10.0.0.2, 10.0.0.1"),
+      Some("https"),
+      Some("example.com"),
+      Some("443"),
+    );
+
+    assert_eq!(info.remote_addr, Some("203.0.113.5".parse().unwrap()));
+    assert_eq!(info.scheme, Some("https".to_owned()));
+    assert_eq!(info.host, Some("example.com".to_owned()));
+    assert_eq!(info.port, Some(443));
+  }
+
+  #[test]
+  fn ignores_forwarded_headers_from_untrusted_peer() {
+    let proxies = TrustedProxies::new(vec![
+      IpCidr::parse("10.0.0.0/8").expect("should parse"),
+    ]);
+
+    let peer: IpAddr = "203.0.113.1".parse().unwrap();
+    let info = proxies.resolve(Some(peer), Some("198.51.100.1"), None, None, None);
+
+    assert_eq!(info, ForwardedInfo::default());
+  }
+}
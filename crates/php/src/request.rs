@@ -65,7 +65,16 @@ impl Request {
     }
 
     // Body
-    // TODO: Streaming bodies with futures::Stream
+    //
+    // This `sys::php_http_request` wrapper predates the `http_handler::Request`
+    // based SAPI pipeline in `embed.rs` and isn't reachable from it - it has no
+    // `mod request;` in lib.rs, so nothing outside this file calls `set_body`/
+    // `body`. Streaming is instead handled on the pipeline that's actually
+    // wired up: `RequestBody`/`StreamedBody` (`body.rs`) feed `php://input`
+    // incrementally from a channel, and `response.rs`'s `body_chunks` lets a
+    // caller drain a response in bounded pieces - see chunk3-6 and chunk8-2.
+    // A `futures::Stream` wasn't used for either since this workspace has no
+    // Cargo manifest to add the dependency to.
     pub fn set_body<T>(&self, body: T)
     where
         T: AsRef<str>
@@ -1,9 +1,25 @@
 use ext_php_rs::zend::SapiGlobals;
 use http_handler::{Request, ResponseBuilderExt, BodyBuffer, ResponseLog, HeaderMap, StatusCode, HeaderName, HeaderValue};
-use bytes::BytesMut;
-use std::{ffi::c_void, path::PathBuf};
+use std::{cell::RefCell, ffi::c_void, path::PathBuf};
 use http_handler::request::Parts;
 
+use crate::body::{BufferedBody, RequestBody};
+use crate::permissions::Permissions;
+use crate::proxy::{ForwardedInfo, TrustedProxies};
+use crate::trace::{Span, TraceContext};
+
+/// Maximum number of recycled [`RequestContext`]s retained per thread by
+/// [`RequestContext::release`].
+///
+/// Requests on a given thread are handled serially, so in practice at most
+/// one context is ever parked here - this just bounds the worst case (e.g.
+/// a thread pool being resized down) so a pool can't pin unbounded memory.
+const POOL_CAPACITY: usize = 32;
+
+thread_local! {
+  static POOL: RefCell<Vec<Box<RequestContext>>> = RefCell::new(Vec::new());
+}
+
 /// The request context for the PHP SAPI.
 ///
 /// This has been redesigned to address all issues in FIXME.md:
@@ -13,40 +29,161 @@ use http_handler::request::Parts;
 #[derive(Debug)]
 pub struct RequestContext {
   request_parts: Parts,
-  request_body: BytesMut,
+  request_body: Box<dyn RequestBody>,
   response_status: StatusCode,
   response_headers: HeaderMap,
   response_body: BodyBuffer,
   response_log: ResponseLog,
   response_exception: Option<String>,
   docroot: PathBuf,
+  trace: TraceContext,
+  span: Span,
+  forwarded: ForwardedInfo,
+  permissions: Permissions,
 }
 
 impl RequestContext {
-  /// Sets the current request context for the PHP SAPI.
+  /// Sets the current request context for the PHP SAPI, fully materializing
+  /// the request body into a [`BufferedBody`] first.
   ///
   /// Uses into_parts() to avoid RefUnwindSafe issues (FIXME.md #1).
-  pub fn for_request<S>(request: Request, docroot: S)
+  pub fn for_request<S>(
+    request: Request,
+    docroot: S,
+    trusted_proxies: TrustedProxies,
+    permissions: Permissions,
+  )
   where
     S: Into<PathBuf>,
   {
     // Use into_parts() to avoid RefUnwindSafe issues (FIXME.md #1)
     let (parts, body) = request.into_parts();
 
-    let context = Box::new(RequestContext {
-      request_parts: parts,
-      request_body: body,
-      response_status: StatusCode::OK,
-      response_headers: HeaderMap::new(),
-      response_body: BodyBuffer::new(),
-      response_log: ResponseLog::new(),
-      response_exception: None,
-      docroot: docroot.into(),
-    });
+    Self::for_parts(
+      parts,
+      Box::new(BufferedBody::new(body)),
+      docroot,
+      trusted_proxies,
+      permissions,
+    )
+  }
+
+  /// Sets the current request context for the PHP SAPI from already-split
+  /// request parts, using `request_body` as the source `php://input` reads
+  /// from instead of assuming it has been fully materialized. This lets a
+  /// caller feed the body incrementally via a [`StreamedBody`] so a large
+  /// upload can be read by PHP with bounded memory.
+  pub fn for_parts<S>(
+    parts: Parts,
+    request_body: Box<dyn RequestBody>,
+    docroot: S,
+    trusted_proxies: TrustedProxies,
+    permissions: Permissions,
+  )
+  where
+    S: Into<PathBuf>,
+  {
+    let traceparent = parts
+      .headers
+      .get("traceparent")
+      .and_then(|v| v.to_str().ok());
+    let tracestate = parts
+      .headers
+      .get("tracestate")
+      .and_then(|v| v.to_str().ok());
+    let trace = TraceContext::from_headers(traceparent, tracestate);
+    let span = trace.open_span(parts.method.as_str(), parts.uri.path());
+
+    let peer = parts
+      .extensions
+      .get::<http_handler::SocketInfo>()
+      .and_then(|socket_info| socket_info.remote)
+      .map(|remote| remote.ip());
+    let header = |name: &str| parts.headers.get(name).and_then(|v| v.to_str().ok());
+    let forwarded = trusted_proxies.resolve(
+      peer,
+      header("X-Forwarded-For"),
+      header("X-Forwarded-Proto"),
+      header("X-Forwarded-Host"),
+      header("X-Forwarded-Port"),
+    );
+
+    let docroot = docroot.into();
+
+    // Reuse a context released by an earlier request on this thread rather
+    // than allocating a fresh one - this skips a heap allocation plus the
+    // HeaderMap/BodyBuffer/ResponseLog allocations nested inside it for the
+    // common case of back-to-back requests on the same thread.
+    let context = match POOL.with(|pool| pool.borrow_mut().pop()) {
+      Some(mut context) => {
+        context.reset(parts, request_body, docroot, trace, span, forwarded, permissions);
+        context
+      }
+      None => Box::new(RequestContext {
+        request_parts: parts,
+        request_body,
+        response_status: StatusCode::OK,
+        response_headers: HeaderMap::new(),
+        response_body: BodyBuffer::new(),
+        response_log: ResponseLog::new(),
+        response_exception: None,
+        docroot,
+        trace,
+        span,
+        forwarded,
+        permissions,
+      }),
+    };
+
     let mut globals = SapiGlobals::get_mut();
     globals.server_context = Box::into_raw(context) as *mut c_void;
   }
 
+  /// Reinitializes a recycled context for a new request, overwriting every
+  /// field so a pooled [`RequestContext`] can't leak state - buffered
+  /// response body, headers, log, exception, status - from the request it
+  /// last served into the next one.
+  fn reset(
+    &mut self,
+    request_parts: Parts,
+    request_body: Box<dyn RequestBody>,
+    docroot: PathBuf,
+    trace: TraceContext,
+    span: Span,
+    forwarded: ForwardedInfo,
+    permissions: Permissions,
+  ) {
+    self.request_parts = request_parts;
+    self.request_body = request_body;
+    self.response_status = StatusCode::OK;
+    self.response_headers = HeaderMap::new();
+    self.response_body = BodyBuffer::new();
+    self.response_log = ResponseLog::new();
+    self.response_exception = None;
+    self.docroot = docroot;
+    self.trace = trace;
+    self.span = span;
+    self.forwarded = forwarded;
+    self.permissions = permissions;
+  }
+
+  /// Returns a context to this thread's pool for reuse by a later request,
+  /// instead of dropping its allocation.
+  ///
+  /// Bounded by [`POOL_CAPACITY`]; anything past the cap is simply dropped.
+  /// Callers only reach this after a request has fully completed - a
+  /// bailout or panic mid-request skips straight to [`EmbedRequestError`]
+  /// without calling `release`, so a context is never returned to the pool
+  /// in a half-updated state.
+  pub fn release(context: Box<RequestContext>) {
+    POOL.with(|pool| {
+      let mut pool = pool.borrow_mut();
+      if pool.len() < POOL_CAPACITY {
+        pool.push(context);
+      }
+    });
+  }
+
   /// Retrieve a mutable reference to the request context
   pub fn current<'a>() -> Option<&'a mut RequestContext> {
     let ptr = {
@@ -79,15 +216,37 @@ impl RequestContext {
     &self.request_parts
   }
 
-  /// Returns a mutable reference to the request body.
-  /// This allows proper consumption of the body (FIXME.md #2).
-  pub fn request_body_mut(&mut self) -> &mut BytesMut {
-    &mut self.request_body
+  /// Returns the distributed trace context associated with this request,
+  /// either parsed from the inbound `traceparent` header or freshly
+  /// generated when one wasn't present.
+  pub fn trace(&self) -> &TraceContext {
+    &self.trace
   }
 
-  /// Returns a reference to the request body.
-  pub fn request_body(&self) -> &BytesMut {
-    &self.request_body
+  /// Returns the client-facing request state resolved from `X-Forwarded-*`
+  /// headers, if the immediate peer was a trusted proxy. All fields are
+  /// `None` when forwarded header resolution is disabled or the peer isn't
+  /// trusted.
+  pub fn forwarded(&self) -> &ForwardedInfo {
+    &self.forwarded
+  }
+
+  /// Returns the capability policy configured for this request, via
+  /// [`Embed::with_permissions`](crate::Embed::with_permissions). Fully
+  /// restrictive (no filesystem roots beyond docroot, no network, no
+  /// environment variables) when none was configured. Its environment
+  /// variable and filesystem rules are enforced (which process environment
+  /// variables reach `$_SERVER`, and `Embed`'s own static-asset reads); its network rules are not yet -
+  /// see [`Permissions`](crate::Permissions)'s documentation for the full
+  /// scope.
+  pub fn permissions(&self) -> &Permissions {
+    &self.permissions
+  }
+
+  /// Returns a mutable reference to the request body source.
+  /// This allows proper consumption of the body (FIXME.md #2).
+  pub fn request_body_mut(&mut self) -> &mut dyn RequestBody {
+    self.request_body.as_mut()
   }
 
   /// Add a header to the response.
@@ -127,7 +286,16 @@ impl RequestContext {
 
   /// Build the final response using the accumulated data.
   /// This properly uses ResponseBuilderExt for logs and exceptions (FIXME.md #3, #4).
-  pub fn build_response(self) -> Result<http_handler::Response, http_handler::Error> {
+  ///
+  /// Takes `&mut self` rather than consuming the context so the caller can
+  /// hand the allocation to [`RequestContext::release`] afterward instead
+  /// of dropping it.
+  pub fn build_response(&mut self) -> Result<http_handler::Response, http_handler::Error> {
+    // Close the span with the final status before it's dropped, so the
+    // downstream APM agent sees the outcome rather than an open span.
+    self.span.finish(self.response_status.as_u16());
+    self.response_log.append(self.span.to_log_line().as_bytes());
+
     // Start building the response
     let mut builder = http_handler::response::Response::builder()
       .status(self.response_status);
@@ -139,10 +307,10 @@ impl RequestContext {
 
     // Add extensions using ResponseBuilderExt
     builder = builder
-      .body_buffer(self.response_body)
-      .log(self.response_log.into_bytes());
+      .body_buffer(std::mem::replace(&mut self.response_body, BodyBuffer::new()))
+      .log(std::mem::replace(&mut self.response_log, ResponseLog::new()).into_bytes());
 
-    if let Some(exception) = self.response_exception {
+    if let Some(exception) = self.response_exception.take() {
       builder = builder.exception(exception);
     }
 
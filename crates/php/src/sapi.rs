@@ -22,7 +22,7 @@ use ext_php_rs::{
 
 use once_cell::sync::OnceCell;
 
-use crate::{EmbedRequestError, EmbedStartError, RequestContext};
+use crate::{EmbedRequestError, EmbedStartError, RequestBody, RequestContext};
 
 // This is a helper to ensure that PHP is initialized and deinitialized at the
 // appropriate times.
@@ -228,6 +228,10 @@ pub extern "C" fn sapi_module_shutdown(
 pub extern "C" fn sapi_module_deactivate() -> c_int {
   let mut globals = SapiGlobals::get_mut();
 
+  // The request body source (buffered or streaming) is dropped along with
+  // the rest of the RequestContext once it's reclaimed after the script
+  // finishes, which closes a StreamedBody's receiver and discards any
+  // unread chunks.
   for i in 0..globals.request_info.argc {
     maybe_efree(unsafe { *globals.request_info.argv.offset(i as isize) }.cast::<u8>());
   }
@@ -304,22 +308,16 @@ pub extern "C" fn sapi_module_read_post(buffer: *mut c_char, length: usize) -> u
   }
 
   // Fixed body reading bug from FIXME.md #2
-  // Now we properly consume from the mutable body instead of cloning
+  // Pulls from the pluggable RequestBody source on demand rather than
+  // assuming the whole body has already been materialized, so a streaming
+  // source only blocks until its next chunk is available.
   RequestContext::current()
-    .map(|ctx| {
-      let body = ctx.request_body_mut();
-      let actual_length = length.min(body.len());
-      if actual_length == 0 {
-        return 0;
-      }
-
-      // Properly consume from the original body buffer
-      let chunk = body.split_to(actual_length);
-
+    .and_then(|ctx| ctx.request_body_mut().read_chunk(length))
+    .map(|chunk| {
       unsafe {
-        std::ptr::copy_nonoverlapping(chunk.as_ptr() as *mut c_char, buffer, actual_length);
+        std::ptr::copy_nonoverlapping(chunk.as_ptr() as *mut c_char, buffer, chunk.len());
       }
-      actual_length
+      chunk.len()
     })
     .unwrap_or(0)
 }
@@ -403,7 +401,22 @@ pub extern "C" fn sapi_module_register_server_variables(vars: *mut ext_php_rs::t
           std::ptr::null_mut()
         };
 
-        env_var(vars, "REQUEST_SCHEME", request_parts.uri.scheme_str().unwrap_or("http"))?;
+        // Expose the distributed trace context so PHP userland doesn't need
+        // to re-parse the inbound traceparent header itself.
+        let trace = ctx.trace();
+        env_var(vars, "TRACE_ID", trace.trace_id())?;
+        env_var(vars, "SPAN_ID", trace.span_id())?;
+        env_var(vars, "TRACE_SAMPLED", if trace.sampled() { "1" } else { "0" })?;
+
+        // When the immediate peer is a trusted proxy, prefer the client-facing
+        // scheme/host/port/address it reported over the raw socket/URI.
+        let forwarded = ctx.forwarded().clone();
+
+        let scheme = forwarded.scheme.as_deref().unwrap_or_else(|| request_parts.uri.scheme_str().unwrap_or("http"));
+        env_var(vars, "REQUEST_SCHEME", scheme)?;
+        if scheme.eq_ignore_ascii_case("https") {
+          env_var(vars, "HTTPS", "on")?;
+        }
         env_var(vars, "CONTEXT_PREFIX", "")?;
         env_var(vars, "SERVER_ADMIN", "webmaster@localhost")?;
         env_var(vars, "GATEWAY_INTERFACE", "CGI/1.1")?;
@@ -421,7 +434,9 @@ pub extern "C" fn sapi_module_register_server_variables(vars: *mut ext_php_rs::t
         env_var(vars, "DOCUMENT_ROOT", docroot_str.clone())?;
         env_var(vars, "CONTEXT_DOCUMENT_ROOT", docroot_str)?;
 
-        if let Ok(server_name) = hostname::get() {
+        if let Some(server_name) = &forwarded.host {
+          env_var(vars, "SERVER_NAME", server_name)?;
+        } else if let Ok(server_name) = hostname::get() {
           if let Some(server_name) = server_name.to_str() {
             env_var(vars, "SERVER_NAME", server_name)?;
           }
@@ -441,10 +456,15 @@ pub extern "C" fn sapi_module_register_server_variables(vars: *mut ext_php_rs::t
         if let Some(socket_info) = request_parts.extensions.get::<http_handler::SocketInfo>() {
           if let Some(local) = socket_info.local {
             env_var(vars, "SERVER_ADDR", local.ip().to_string())?;
-            env_var(vars, "SERVER_PORT", local.port().to_string())?;
+            env_var(
+              vars,
+              "SERVER_PORT",
+              forwarded.port.unwrap_or(local.port()).to_string(),
+            )?;
           }
           if let Some(remote) = socket_info.remote {
-            env_var(vars, "REMOTE_ADDR", remote.ip().to_string())?;
+            let remote_addr = forwarded.remote_addr.unwrap_or(remote.ip());
+            env_var(vars, "REMOTE_ADDR", remote_addr.to_string())?;
             env_var(vars, "REMOTE_PORT", remote.port().to_string())?;
           }
         }
@@ -461,6 +481,38 @@ pub extern "C" fn sapi_module_register_server_variables(vars: *mut ext_php_rs::t
           env_var_c(vars, "QUERY_STRING", req_info.query_string)?;
         }
 
+        // `REMOTE_USER` is only derivable for `Basic` auth, whose username is
+        // parsed out separately - `Digest`'s username lives unparsed inside
+        // its challenge string, and other schemes (`Bearer`, `Negotiate`,
+        // ...) carry no username at all, only `HTTP_AUTHORIZATION` (set by
+        // the generic header loop above).
+        if !req_info.auth_user.is_null() {
+          env_var_c(vars, "PHP_AUTH_USER", req_info.auth_user)?;
+          env_var_c(vars, "REMOTE_USER", req_info.auth_user)?;
+          env_var(vars, "AUTH_TYPE", "Basic")?;
+        }
+
+        if !req_info.auth_password.is_null() {
+          env_var_c(vars, "PHP_AUTH_PW", req_info.auth_password)?;
+        }
+
+        if !req_info.auth_digest.is_null() {
+          env_var_c(vars, "PHP_AUTH_DIGEST", req_info.auth_digest)?;
+          env_var(vars, "AUTH_TYPE", "Digest")?;
+        }
+
+        // Only pass through the process environment variables the
+        // configured Permissions sandbox allowlists, rather than importing
+        // the whole process environment (which could leak host secrets
+        // into semi-trusted tenant code) - see `Permissions`' documentation
+        // for the rest of its, currently unenforced, scope.
+        let permissions = ctx.permissions();
+        for (key, value) in std::env::vars() {
+          if permissions.allows_env_var(&key) {
+            env_var(vars, key, value)?;
+          }
+        }
+
         Ok(())
       })
       .ok();
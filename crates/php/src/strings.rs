@@ -49,36 +49,91 @@ pub(crate) fn drop_str(ptr: *mut c_char) {
   drop(reclaim_str(ptr));
 }
 
-pub(crate) fn translate_path<D, P>(docroot: D, request_uri: P) -> Result<PathBuf, EmbedRequestError>
+/// Resolves `request_uri` to a file under `docroot`, trying each name in
+/// `directory_index` in order - à la a web server's `DirectoryIndex`
+/// directive - when `request_uri` ends in `/`.
+pub(crate) fn translate_path<D, P>(
+  docroot: D,
+  request_uri: P,
+  directory_index: &[String],
+) -> Result<PathBuf, EmbedRequestError>
 where
   D: AsRef<Path>,
   P: AsRef<Path>,
 {
-  let docroot = docroot.as_ref().to_path_buf();
-  let request_uri = request_uri.as_ref();
-
-  let relative_uri = request_uri.strip_prefix("/").map_err(|_| {
-    let uri = request_uri.display().to_string();
-    EmbedRequestError::ExpectedAbsoluteRequestUri(uri)
-  })?;
+  let docroot = docroot
+    .as_ref()
+    .canonicalize()
+    .map_err(|_| EmbedRequestError::ScriptNotFound(docroot.as_ref().display().to_string()))?;
 
-  let exact = docroot.join(relative_uri);
+  let request_uri = request_uri.as_ref();
 
   // NOTE: String conversion is necessary. If Path::ends_with("/") is used it
   // will discard the trailing slash first.
-  if request_uri.display().to_string().ends_with("/") {
-    try_path(exact.join("index.php")).or_else(|_| try_path(exact))
+  let uri_string = request_uri.display().to_string();
+
+  let relative_uri = uri_string
+    .strip_prefix('/')
+    .ok_or_else(|| EmbedRequestError::ExpectedAbsoluteRequestUri(uri_string.clone()))?;
+
+  let decoded = decode_request_path(relative_uri)?;
+  let exact = docroot.join(decoded);
+
+  if uri_string.ends_with('/') {
+    directory_index
+      .iter()
+      .find_map(|index| try_path(exact.join(index), &docroot).ok())
+      .ok_or_else(|| EmbedRequestError::DirectoryIndexNotFound(exact.display().to_string()))
   } else {
-    try_path(exact)
+    try_path(exact, &docroot)
+  }
+}
+
+/// Percent-decodes `path`, rejecting a malformed `%XX` escape, an escape
+/// decoding to a NUL byte, or a decoded byte sequence that isn't valid
+/// UTF-8 - any of which could otherwise be used to smuggle a traversal
+/// sequence like `%2e%2e` past a naive string check before it reaches the
+/// filesystem.
+fn decode_request_path(path: &str) -> Result<String, EmbedRequestError> {
+  let bytes = path.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+
+  while i < bytes.len() {
+    if bytes[i] == b'%' {
+      let byte = bytes
+        .get(i + 1..i + 3)
+        .and_then(|hex| std::str::from_utf8(hex).ok())
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| EmbedRequestError::InvalidRequestUri(path.to_owned()))?;
+
+      if byte == 0 {
+        return Err(EmbedRequestError::InvalidRequestUri(path.to_owned()));
+      }
+
+      decoded.push(byte);
+      i += 3;
+    } else {
+      decoded.push(bytes[i]);
+      i += 1;
+    }
   }
+
+  String::from_utf8(decoded).map_err(|_| EmbedRequestError::InvalidRequestUri(path.to_owned()))
 }
 
-fn try_path<P: AsRef<Path>>(path: P) -> Result<PathBuf, EmbedRequestError> {
+fn try_path<P: AsRef<Path>>(path: P, docroot: &Path) -> Result<PathBuf, EmbedRequestError> {
   let path = path.as_ref();
   let true_path = path
     .canonicalize()
     .map_err(|_| EmbedRequestError::ScriptNotFound(path.display().to_string()))?;
 
+  if !true_path.starts_with(docroot) {
+    return Err(EmbedRequestError::PathTraversal(
+      path.display().to_string(),
+    ));
+  }
+
   if true_path.is_file() {
     Ok(true_path)
   } else {
@@ -93,6 +148,10 @@ mod test {
   use super::*;
   use crate::MockRoot;
 
+  fn default_index() -> Vec<String> {
+    vec!["index.php".to_string()]
+  }
+
   #[test]
   fn test_translate_path() {
     let docroot = MockRoot::builder()
@@ -102,14 +161,115 @@ mod test {
       .expect("should prepare docroot");
 
     assert_eq!(
-      translate_path(docroot.clone(), "/foo/"),
+      translate_path(docroot.clone(), "/foo/", &default_index()),
       Ok(docroot.join("foo/index.php"))
     );
     assert_eq!(
-      translate_path(docroot.clone(), "/foo"),
+      translate_path(docroot.clone(), "/foo", &default_index()),
       Err(EmbedRequestError::ScriptNotFound(
         docroot.join("foo").display().to_string()
       ))
     );
   }
+
+  #[test]
+  fn rejects_dot_dot_traversal_outside_docroot() {
+    let docroot = MockRoot::builder()
+      .file("/index.php", "<?php echo \"index\"; ?>")
+      .build()
+      .expect("should prepare docroot");
+
+    // A real, canonicalizable file just outside the docroot, so the
+    // traversal attempt resolves to a path that genuinely exists, rather
+    // than being masked by a plain ScriptNotFound.
+    let outside = docroot
+      .parent()
+      .expect("docroot should have a parent")
+      .join("outside-secret.php");
+    std::fs::write(&outside, "<?php echo \"secret\"; ?>").expect("should write file");
+
+    assert!(matches!(
+      translate_path(docroot.clone(), "/../outside-secret.php", &default_index()),
+      Err(EmbedRequestError::PathTraversal(_))
+    ));
+
+    let _ = std::fs::remove_file(&outside);
+  }
+
+  #[test]
+  fn rejects_percent_encoded_dot_dot_traversal() {
+    let docroot = MockRoot::builder()
+      .file("/index.php", "<?php echo \"index\"; ?>")
+      .build()
+      .expect("should prepare docroot");
+
+    let outside = docroot
+      .parent()
+      .expect("docroot should have a parent")
+      .join("outside-secret-encoded.php");
+    std::fs::write(&outside, "<?php echo \"secret\"; ?>").expect("should write file");
+
+    assert!(matches!(
+      translate_path(docroot.clone(), "/%2e%2e/outside-secret-encoded.php", &default_index()),
+      Err(EmbedRequestError::PathTraversal(_))
+    ));
+
+    let _ = std::fs::remove_file(&outside);
+  }
+
+  #[test]
+  fn rejects_encoded_nul_byte() {
+    let docroot = MockRoot::builder()
+      .file("/index.php", "<?php echo \"index\"; ?>")
+      .build()
+      .expect("should prepare docroot");
+
+    assert!(matches!(
+      translate_path(docroot.clone(), "/foo%00.php", &default_index()),
+      Err(EmbedRequestError::InvalidRequestUri(_))
+    ));
+  }
+
+  #[test]
+  fn rejects_malformed_percent_escape() {
+    let docroot = MockRoot::builder()
+      .file("/index.php", "<?php echo \"index\"; ?>")
+      .build()
+      .expect("should prepare docroot");
+
+    assert!(matches!(
+      translate_path(docroot.clone(), "/foo%zz.php", &default_index()),
+      Err(EmbedRequestError::InvalidRequestUri(_))
+    ));
+  }
+
+  #[test]
+  fn tries_each_directory_index_candidate_in_order() {
+    // A docroot of its own: the shared default one gets `/foo/index.php`
+    // from `test_translate_path`, which would win over `index.html`.
+    let docroot = crate::MockRootBuilder::new(std::env::temp_dir().join("php-temp-dir-index-order"))
+      .file("/foo/index.html", "<html>sub</html>")
+      .build()
+      .expect("should prepare docroot");
+
+    let index = vec!["index.php".to_string(), "index.html".to_string()];
+
+    assert_eq!(
+      translate_path(docroot.clone(), "/foo/", &index),
+      Ok(docroot.join("foo/index.html"))
+    );
+  }
+
+  #[test]
+  fn errors_when_no_directory_index_candidate_exists() {
+    let docroot = crate::MockRootBuilder::new(std::env::temp_dir().join("php-temp-dir-no-index"))
+      .file("/foo/readme.txt", "not an index")
+      .build()
+      .expect("should prepare docroot");
+
+    assert!(matches!(
+      translate_path(docroot.clone(), "/foo/", &default_index()),
+      Err(EmbedRequestError::DirectoryIndexNotFound(_))
+    ));
+  }
 }
@@ -15,12 +15,12 @@ impl MockRoot {
   pub fn new<D, H>(docroot: D, files: H) -> Result<Self, Error>
   where
     D: AsRef<Path>,
-    H: Into<HashMap<PathBuf, String>>,
+    H: Into<HashMap<PathBuf, Vec<u8>>>,
   {
     let docroot = docroot.as_ref();
     create_dir_all(docroot)?;
 
-    let map: HashMap<PathBuf, String> = files.into();
+    let map: HashMap<PathBuf, Vec<u8>> = files.into();
     for (path, contents) in map.iter() {
       let stripped = path.strip_prefix("/").unwrap_or(path);
 
@@ -30,7 +30,7 @@ impl MockRoot {
       }
 
       let mut file = File::create(file_path)?;
-      file.write_all(contents.as_bytes())?;
+      file.write_all(contents)?;
     }
 
     // This unwrap should be safe due to creating the docroot base dir above.
@@ -76,7 +76,7 @@ impl AsRef<Path> for MockRoot {
 
 /// A builder for creating a MockRoot with a specified document root and files.
 #[derive(Debug)]
-pub struct MockRootBuilder(PathBuf, HashMap<PathBuf, String>);
+pub struct MockRootBuilder(PathBuf, HashMap<PathBuf, Vec<u8>>);
 
 impl MockRootBuilder {
   /// Create a new MockRootBuilder with the specified document root.
@@ -87,11 +87,22 @@ impl MockRootBuilder {
     Self(docroot.as_ref().to_owned(), HashMap::new())
   }
 
-  /// Add a file to the mock document root.
-  pub fn file<P, C>(mut self, path: P, contents: C) -> MockRootBuilder
+  /// Add a text file to the mock document root.
+  pub fn file<P, C>(self, path: P, contents: C) -> MockRootBuilder
   where
     P: AsRef<Path>,
     C: Into<String>,
+  {
+    self.file_bytes(path, contents.into().into_bytes())
+  }
+
+  /// Add a binary file to the mock document root, for fixtures like images
+  /// or other non-UTF-8 assets that [`file`](Self::file)'s `String`
+  /// contents can't represent.
+  pub fn file_bytes<P, C>(mut self, path: P, contents: C) -> MockRootBuilder
+  where
+    P: AsRef<Path>,
+    C: Into<Vec<u8>>,
   {
     let path = path.as_ref().to_owned();
     let contents = contents.into();
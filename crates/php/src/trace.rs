@@ -0,0 +1,208 @@
+use std::{
+  fmt::Write as _,
+  time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// A W3C `traceparent`/`tracestate` context for a single request.
+///
+/// See <https://www.w3.org/TR/trace-context/> for the header formats this
+/// parses and generates. This lets php-node participate in a distributed
+/// trace started by an upstream proxy or APM agent without PHP userland
+/// needing to re-implement header parsing.
+#[derive(Debug, Clone)]
+pub struct TraceContext {
+  trace_id: [u8; 16],
+  span_id: [u8; 8],
+  sampled: bool,
+  tracestate: Option<String>,
+}
+
+impl TraceContext {
+  /// Parses a `traceparent` header of the form
+  /// `00-<32-hex-trace-id>-<16-hex-parent-id>-<2-hex-flags>`, passing through
+  /// `tracestate` unmodified. Returns `None` if the header is missing or
+  /// malformed, in which case callers should fall back to [`TraceContext::generate`].
+  pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+    let mut parts = traceparent.trim().split('-');
+
+    if parts.next()? != "00" {
+      return None;
+    }
+
+    let trace_id = parse_hex::<16>(parts.next()?)?;
+    let span_id = parse_hex::<8>(parts.next()?)?;
+    let flags = parse_hex::<1>(parts.next()?)?;
+
+    if parts.next().is_some() || trace_id == [0; 16] || span_id == [0; 8] {
+      return None;
+    }
+
+    Some(Self {
+      trace_id,
+      span_id,
+      sampled: flags[0] & 0x01 != 0,
+      tracestate: tracestate.map(str::to_owned),
+    })
+  }
+
+  /// Generates a fresh trace id and span id, for use when no valid
+  /// `traceparent` header was supplied by the caller.
+  pub fn generate() -> Self {
+    Self {
+      trace_id: random_bytes(),
+      span_id: random_bytes(),
+      sampled: true,
+      tracestate: None,
+    }
+  }
+
+  /// Parses the inbound `traceparent`/`tracestate` headers, falling back to
+  /// a freshly generated trace context if `traceparent` is absent or
+  /// malformed.
+  pub fn from_headers(traceparent: Option<&str>, tracestate: Option<&str>) -> Self {
+    traceparent
+      .and_then(|header| Self::parse(header, tracestate))
+      .unwrap_or_else(Self::generate)
+  }
+
+  /// Returns the trace id as a lowercase hex string.
+  pub fn trace_id(&self) -> String {
+    to_hex(&self.trace_id)
+  }
+
+  /// Returns the current span id as a lowercase hex string.
+  pub fn span_id(&self) -> String {
+    to_hex(&self.span_id)
+  }
+
+  /// Returns whether the `sampled` flag was set on the inbound context.
+  pub fn sampled(&self) -> bool {
+    self.sampled
+  }
+
+  /// Returns the passed-through `tracestate` header value, if any.
+  pub fn tracestate(&self) -> Option<&str> {
+    self.tracestate.as_deref()
+  }
+
+  /// Opens a span for the current request. Dropping it records nothing: it
+  /// is closed with [`Span::finish`] and recorded with [`Span::to_log_line`]
+  /// when the request's response is built.
+  pub fn open_span(&self, method: impl Into<String>, path: impl Into<String>) -> Span {
+    Span {
+      trace_id: self.trace_id(),
+      span_id: self.span_id(),
+      method: method.into(),
+      path: path.into(),
+      status: None,
+      start: Instant::now(),
+    }
+  }
+}
+
+/// An in-flight span around a single request's script execution. Record the
+/// final status with [`Span::finish`] before it is dropped so the closing
+/// record reflects the outcome rather than an in-progress span.
+#[derive(Debug)]
+pub struct Span {
+  trace_id: String,
+  span_id: String,
+  method: String,
+  path: String,
+  status: Option<u16>,
+  start: Instant,
+}
+
+impl Span {
+  /// Records the final status code of the request this span covers.
+  pub fn finish(&mut self, status: u16) {
+    self.status = Some(status);
+  }
+
+  /// Renders the closed span as a single log line suitable for handing to an
+  /// APM agent tailing the PHP request log.
+  pub fn to_log_line(&self) -> String {
+    format!(
+      "trace_id={} span_id={} method={} path={} status={} duration_ms={}",
+      self.trace_id,
+      self.span_id,
+      self.method,
+      self.path,
+      self.status.map(|s| s.to_string()).unwrap_or_else(|| "-".into()),
+      self.start.elapsed().as_millis(),
+    )
+  }
+}
+
+fn parse_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+  if s.len() != N * 2 {
+    return None;
+  }
+
+  let mut out = [0u8; N];
+  for (i, byte) in out.iter_mut().enumerate() {
+    *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+  }
+  Some(out)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  let mut out = String::with_capacity(bytes.len() * 2);
+  for byte in bytes {
+    let _ = write!(out, "{:02x}", byte);
+  }
+  out
+}
+
+// No crypto-strength RNG is available without pulling in a new dependency,
+// so mix wall-clock entropy with the hashed address of a fresh stack value.
+// This is fine for trace/span ids, which only need to be unique, not secret.
+fn random_bytes<const N: usize>() -> [u8; N] {
+  let nanos = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_nanos())
+    .unwrap_or_default();
+
+  let salt = &nanos as *const _ as u64;
+
+  let mut out = [0u8; N];
+  let mut state = (nanos as u64) ^ salt.rotate_left(17);
+  for byte in out.iter_mut() {
+    // xorshift64*
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    *byte = (state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 56) as u8;
+  }
+  out
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_valid_traceparent() {
+    let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let ctx = TraceContext::parse(header, Some("vendor=value")).expect("should parse");
+
+    assert_eq!(ctx.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(ctx.span_id(), "00f067aa0ba902b7");
+    assert!(ctx.sampled());
+    assert_eq!(ctx.tracestate(), Some("vendor=value"));
+  }
+
+  #[test]
+  fn rejects_malformed_traceparent() {
+    assert!(TraceContext::parse("not-a-traceparent", None).is_none());
+    assert!(TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01", None).is_none());
+    assert!(TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01", None).is_none());
+  }
+
+  #[test]
+  fn falls_back_to_generated_context() {
+    let ctx = TraceContext::from_headers(None, None);
+    assert_eq!(ctx.trace_id().len(), 32);
+    assert_eq!(ctx.span_id().len(), 16);
+  }
+}
@@ -0,0 +1,176 @@
+//! `Accept-Encoding` negotiation and response body compression, mirroring
+//! what actix-web's `ContentEncoding`/`Compress` middleware does for an
+//! outgoing response.
+
+use std::io::Write;
+
+/// The minimum response body size, in bytes, worth spending CPU to
+/// compress. Small bodies tend to get larger once gzip/deflate/brotli
+/// framing overhead is added, so anything below this is left as-is.
+pub(crate) const MIN_COMPRESSIBLE_SIZE: usize = 860;
+
+/// A content coding this crate knows how to apply to a response body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ContentEncoding {
+  /// `gzip` - DEFLATE wrapped in the gzip container format.
+  Gzip,
+  /// `deflate` - a zlib-wrapped DEFLATE stream.
+  Deflate,
+  /// `br` - Brotli.
+  Brotli,
+}
+
+impl ContentEncoding {
+  /// Returns the `Content-Encoding` token for this coding, e.g. `"gzip"`.
+  pub(crate) fn as_str(&self) -> &'static str {
+    match self {
+      ContentEncoding::Gzip => "gzip",
+      ContentEncoding::Deflate => "deflate",
+      ContentEncoding::Brotli => "br",
+    }
+  }
+
+  /// Compresses `body` using this coding.
+  pub(crate) fn compress(&self, body: &[u8]) -> Vec<u8> {
+    match self {
+      ContentEncoding::Gzip => {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body).expect("in-memory writer should not fail");
+        encoder.finish().expect("in-memory writer should not fail")
+      }
+      ContentEncoding::Deflate => {
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body).expect("in-memory writer should not fail");
+        encoder.finish().expect("in-memory writer should not fail")
+      }
+      ContentEncoding::Brotli => {
+        let mut output = Vec::new();
+        let params = brotli::enc::BrotliEncoderParams::default();
+        brotli::BrotliCompress(&mut &body[..], &mut output, &params)
+          .expect("in-memory writer should not fail");
+        output
+      }
+    }
+  }
+}
+
+/// A single `Accept-Encoding` candidate and its quality value.
+struct Candidate<'a> {
+  token: &'a str,
+  q: f32,
+}
+
+/// Parses an `Accept-Encoding` header and picks the best content coding
+/// this crate supports, honoring q-values, `identity`, and `*`.
+///
+/// Returns `None` when no supported coding should be applied - either
+/// because `accept_encoding` is empty, or because it explicitly rejects
+/// every coding this crate supports (e.g. `gzip;q=0, deflate;q=0, br;q=0`
+/// with no `*` fallback). This is also the correct outcome for a header of
+/// just `identity`, since "don't transform the body" is exactly what
+/// leaving it uncompressed means.
+///
+/// On a tie, prefers whichever coding compresses best: brotli, then gzip,
+/// then deflate.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(negotiate("gzip, br;q=0.5"), Some(ContentEncoding::Gzip));
+/// assert_eq!(negotiate("gzip;q=0, *"), Some(ContentEncoding::Brotli));
+/// assert_eq!(negotiate("identity"), None);
+/// ```
+pub(crate) fn negotiate(accept_encoding: &str) -> Option<ContentEncoding> {
+  let candidates: Vec<Candidate> = accept_encoding
+    .split(',')
+    .filter_map(|part| {
+      let mut segments = part.split(';').map(str::trim);
+      let token = segments.next()?;
+
+      if token.is_empty() {
+        return None;
+      }
+
+      let q = segments
+        .find_map(|segment| segment.strip_prefix("q=").and_then(|v| v.trim().parse::<f32>().ok()))
+        .unwrap_or(1.0);
+
+      Some(Candidate { token, q })
+    })
+    .collect();
+
+  let q_for = |token: &str| -> Option<f32> {
+    candidates
+      .iter()
+      .find(|candidate| candidate.token.eq_ignore_ascii_case(token))
+      .map(|candidate| candidate.q)
+      .or_else(|| {
+        candidates
+          .iter()
+          .find(|candidate| candidate.token == "*")
+          .map(|candidate| candidate.q)
+      })
+  };
+
+  let mut best: Option<(ContentEncoding, f32)> = None;
+
+  for encoding in [ContentEncoding::Brotli, ContentEncoding::Gzip, ContentEncoding::Deflate] {
+    if let Some(q) = q_for(encoding.as_str()) {
+      let improves_on_best = match best {
+        Some((_, best_q)) => q > best_q,
+        None => true,
+      };
+
+      if q > 0.0 && improves_on_best {
+        best = Some((encoding, q));
+      }
+    }
+  }
+
+  best.map(|(encoding, _)| encoding)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn picks_the_only_supported_coding() {
+    assert_eq!(negotiate("gzip"), Some(ContentEncoding::Gzip));
+  }
+
+  #[test]
+  fn prefers_higher_q_value() {
+    assert_eq!(negotiate("gzip;q=0.2, deflate;q=0.8"), Some(ContentEncoding::Deflate));
+  }
+
+  #[test]
+  fn breaks_ties_by_compression_quality() {
+    assert_eq!(negotiate("deflate, gzip, br"), Some(ContentEncoding::Brotli));
+  }
+
+  #[test]
+  fn ignores_codings_explicitly_rejected_with_q_zero() {
+    assert_eq!(negotiate("br;q=0, gzip"), Some(ContentEncoding::Gzip));
+  }
+
+  #[test]
+  fn honors_wildcard_fallback() {
+    assert_eq!(negotiate("gzip;q=0, *;q=0.5"), Some(ContentEncoding::Brotli));
+  }
+
+  #[test]
+  fn returns_none_for_identity_only() {
+    assert_eq!(negotiate("identity"), None);
+  }
+
+  #[test]
+  fn returns_none_when_everything_is_rejected() {
+    assert_eq!(negotiate("gzip;q=0, deflate;q=0, br;q=0"), None);
+  }
+
+  #[test]
+  fn returns_none_for_empty_header() {
+    assert_eq!(negotiate(""), None);
+  }
+}
@@ -161,6 +161,24 @@ impl PhpHeaders {
     self.headers.get_line(&key)
   }
 
+  /// Get every `Set-Cookie` value, as a convenience over `getAll` for the
+  /// one header where consumers should always treat values as an array and
+  /// never join them onto one line.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const headers = new Headers();
+  /// headers.add('Set-Cookie', 'a=1');
+  /// headers.add('Set-Cookie', 'b=2');
+  ///
+  /// console.log(headers.getSetCookies()); // ['a=1', 'b=2']
+  /// ```
+  #[napi]
+  pub fn get_set_cookies(&self) -> Vec<String> {
+    self.headers.get_set_cookies()
+  }
+
   /// Check if a header key exists.
   ///
   /// # Examples
@@ -238,6 +256,38 @@ impl PhpHeaders {
     self.headers.clear()
   }
 
+  /// Get the parsed media type from the `Content-Type` header, or `null`
+  /// if it's absent or malformed, without re-parsing the raw string in JS.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const headers = new Headers();
+  /// headers.set('Content-Type', 'application/json; charset=utf-8');
+  ///
+  /// console.log(headers.contentType); // application/json
+  /// ```
+  #[napi(getter)]
+  pub fn content_type(&self) -> Option<String> {
+    self.headers.content_type().map(|content_type| content_type.media_type)
+  }
+
+  /// Get the parsed `Content-Length` header, or `null` if it's absent or
+  /// not a valid non-negative integer.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const headers = new Headers();
+  /// headers.set('Content-Length', '13');
+  ///
+  /// console.log(headers.contentLength); // 13
+  /// ```
+  #[napi(getter)]
+  pub fn content_length(&self) -> Option<u32> {
+    self.headers.content_length().and_then(|len| u32::try_from(len).ok())
+  }
+
   /// Get the number of header entries.
   ///
   /// # Examples
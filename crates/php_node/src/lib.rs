@@ -1,14 +1,20 @@
 #[macro_use]
 extern crate napi_derive;
 
+mod encoding;
 mod headers;
+mod listener;
 mod request;
 mod response;
 mod rewriter;
 mod runtime;
+mod stream;
+mod upstream;
 
 pub use headers::PhpHeaders;
+pub use listener::{PhpListenOptions, PhpListenerHandle};
 pub use request::PhpRequest;
 pub use response::PhpResponse;
 pub use rewriter::PhpRewriter;
 pub use runtime::PhpRuntime;
+pub use stream::PhpRequestBodyStream;
@@ -0,0 +1,195 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use napi::bindgen_prelude::*;
+use napi::{Error, Result};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+
+use php::{Embed, Handler, Request, RequestBuilder};
+
+/// Options for starting a built-in HTTP listener.
+#[napi(object)]
+#[derive(Default)]
+pub struct PhpListenOptions {
+  /// The host to bind to. Defaults to `127.0.0.1`.
+  pub host: Option<String>,
+  /// The port to bind to. Defaults to choosing any available port.
+  pub port: Option<u16>,
+}
+
+/// A running built-in HTTP listener, returned by `Php#listen`.
+///
+/// Dropping the runtime does not stop the listener; call `stop()` to shut
+/// it down, or let the process exit.
+#[napi(js_name = "Listener")]
+pub struct PhpListenerHandle {
+  address: String,
+  shutdown: Arc<Notify>,
+  // Keeps the runtime the accept loop runs on alive after the `Php`
+  // instance that started it is dropped.
+  _runtime: Arc<tokio::runtime::Runtime>,
+}
+
+#[napi]
+impl PhpListenerHandle {
+  /// The address the listener is bound to, e.g. `127.0.0.1:3000`.
+  #[napi(getter, enumerable = true)]
+  pub fn address(&self) -> String {
+    self.address.clone()
+  }
+
+  /// Stop accepting new connections. In-flight requests are allowed to
+  /// finish.
+  #[napi]
+  pub fn stop(&self) {
+    self.shutdown.notify_one();
+  }
+}
+
+/// Binds a TCP listener and spawns an accept loop on `runtime` that
+/// dispatches each connection through `embed` using `hyper`'s HTTP/1
+/// server, converting every request/response across the `php::Request`/
+/// `http_handler` boundary instead of the N-API boundary.
+pub(crate) fn spawn_listener(
+  runtime: Arc<tokio::runtime::Runtime>,
+  embed: Arc<Embed>,
+  options: PhpListenOptions,
+) -> Result<PhpListenerHandle> {
+  let host = options.host.unwrap_or_else(|| "127.0.0.1".to_owned());
+  let port = options.port.unwrap_or_default();
+
+  // Bind synchronously so a bad host/port is reported immediately, rather
+  // than surfacing asynchronously from inside the spawned task.
+  let std_listener = std::net::TcpListener::bind((host.as_str(), port))
+    .map_err(|err| Error::from_reason(format!("Failed to bind listener: {}", err)))?;
+  std_listener
+    .set_nonblocking(true)
+    .map_err(|err| Error::from_reason(err.to_string()))?;
+
+  let local_addr = std_listener
+    .local_addr()
+    .map_err(|err| Error::from_reason(err.to_string()))?;
+
+  let shutdown = Arc::new(Notify::new());
+
+  {
+    let shutdown = shutdown.clone();
+    runtime.spawn(async move {
+      let listener = match TcpListener::from_std(std_listener) {
+        Ok(listener) => listener,
+        Err(_err) => return,
+      };
+
+      loop {
+        tokio::select! {
+          _ = shutdown.notified() => break,
+          accepted = listener.accept() => {
+            let (stream, remote_addr) = match accepted {
+              Ok(accepted) => accepted,
+              Err(_err) => continue,
+            };
+
+            let embed = embed.clone();
+
+            tokio::spawn(async move {
+              let io = TokioIo::new(stream);
+              let service = service_fn(move |request: hyper::Request<hyper::body::Incoming>| {
+                let embed = embed.clone();
+                async move { handle_connection(embed, request, local_addr, remote_addr).await }
+              });
+
+              let _ = http1::Builder::new()
+                .serve_connection(io, service)
+                .await;
+            });
+          }
+        }
+      }
+    });
+  }
+
+  Ok(PhpListenerHandle {
+    address: local_addr.to_string(),
+    shutdown,
+    _runtime: runtime,
+  })
+}
+
+// Converts an incoming hyper request into a `php::Request`, dispatches it
+// through `embed.handle`, and converts the resulting `php::Response` back
+// into a hyper response.
+async fn handle_connection(
+  embed: Arc<Embed>,
+  request: hyper::Request<hyper::body::Incoming>,
+  local_addr: SocketAddr,
+  remote_addr: SocketAddr,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+  let (parts, body) = request.into_parts();
+
+  let body = match body.collect().await {
+    Ok(collected) => collected.to_bytes(),
+    Err(_err) => {
+      return Ok(
+        hyper::Response::builder()
+          .status(400)
+          .body(Full::new(Bytes::from_static(b"Bad Request")))
+          .unwrap(),
+      )
+    }
+  };
+
+  let mut builder: RequestBuilder = Request::builder()
+    .method(parts.method.as_str())
+    .url(&parts.uri.to_string())
+    .local_socket(&local_addr.to_string())
+    .remote_socket(&remote_addr.to_string())
+    .body(body.as_ref());
+
+  for (name, value) in parts.headers.iter() {
+    if let Ok(value) = value.to_str() {
+      builder = builder.header(name.as_str(), value);
+    }
+  }
+
+  let request = match builder.build() {
+    Ok(request) => request,
+    Err(_err) => {
+      return Ok(
+        hyper::Response::builder()
+          .status(400)
+          .body(Full::new(Bytes::from_static(b"Bad Request")))
+          .unwrap(),
+      )
+    }
+  };
+
+  let response = match embed.handle(request).await {
+    Ok(response) => response,
+    Err(_err) => {
+      return Ok(
+        hyper::Response::builder()
+          .status(500)
+          .body(Full::new(Bytes::from_static(b"Internal Server Error")))
+          .unwrap(),
+      )
+    }
+  };
+
+  let mut builder = hyper::Response::builder().status(response.status());
+
+  for (name, value) in response.headers().iter_lines() {
+    builder = builder.header(name, value);
+  }
+
+  Ok(
+    builder
+      .body(Full::new(Bytes::copy_from_slice(response.body())))
+      .unwrap(),
+  )
+}
@@ -3,8 +3,27 @@ use napi::Result;
 
 use php::Response;
 
+use crate::encoding::{self, MIN_COMPRESSIBLE_SIZE};
 use crate::PhpHeaders;
 
+/// Controls whether and how a [`PhpResponse`]'s body is compressed against
+/// `accept_encoding`.
+#[napi]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhpResponseEncoding {
+  /// Negotiate the best supported coding from `accept_encoding`, and
+  /// compress the body accordingly, as long as it's at least
+  /// [`MIN_COMPRESSIBLE_SIZE`] bytes. This is the default.
+  #[default]
+  Auto,
+  /// Never compress the body, regardless of `accept_encoding`.
+  None,
+  /// Always compress the body, using the best coding `accept_encoding`
+  /// accepts, falling back to gzip if `accept_encoding` wasn't given or
+  /// accepts nothing this crate supports.
+  Force,
+}
+
 /// Options for creating a new PHP response.
 #[napi(object)]
 #[derive(Default)]
@@ -19,6 +38,12 @@ pub struct PhpResponseOptions {
   pub log: Option<Uint8Array>,
   /// The exception for the response.
   pub exception: Option<String>,
+  /// Controls whether and how the body is compressed. Defaults to `Auto`.
+  pub encoding: Option<PhpResponseEncoding>,
+  /// The originating request's `Accept-Encoding` header, used to negotiate
+  /// a coding when `encoding` is `Auto` or `Force`. Compression is skipped
+  /// if this isn't given and `encoding` is `Auto`.
+  pub accept_encoding: Option<String>,
 }
 
 /// A PHP response.
@@ -49,6 +74,18 @@ impl PhpResponse {
   ///   body: new Uint8Array([1, 2, 3, 4])
   /// });
   /// ```
+  ///
+  /// Passing `acceptEncoding` (the originating request's `Accept-Encoding`
+  /// header) lets the body be compressed automatically:
+  ///
+  /// ```js
+  /// const response = new Response({
+  ///   body: new Uint8Array(2000),
+  ///   acceptEncoding: 'gzip, br;q=0.5'
+  /// });
+  ///
+  /// console.log(response.headers.get('Content-Encoding'));
+  /// ```
   #[napi(constructor)]
   pub fn constructor(options: Option<PhpResponseOptions>) -> Result<Self> {
     let options = options.unwrap_or_default();
@@ -58,12 +95,50 @@ impl PhpResponse {
       builder.status(status);
     }
 
+    let already_encoded = options
+      .headers
+      .as_ref()
+      .is_some_and(|headers| headers.has("Content-Encoding".to_string()));
+
     if let Some(headers) = options.headers {
       builder = builder.headers(headers);
     }
 
     if let Some(body) = options.body {
-      builder.body(body.as_ref());
+      let body = body.as_ref();
+
+      let coding = if already_encoded {
+        None
+      } else {
+        match options.encoding.unwrap_or_default() {
+          PhpResponseEncoding::None => None,
+          PhpResponseEncoding::Auto => {
+            if body.len() < MIN_COMPRESSIBLE_SIZE {
+              None
+            } else {
+              options.accept_encoding.as_deref().and_then(encoding::negotiate)
+            }
+          }
+          PhpResponseEncoding::Force => options
+            .accept_encoding
+            .as_deref()
+            .and_then(encoding::negotiate)
+            .or(Some(encoding::ContentEncoding::Gzip)),
+        }
+      };
+
+      match coding {
+        Some(coding) => {
+          let compressed = coding.compress(body);
+          builder = builder
+            .header("Content-Encoding", coding.as_str())
+            .header("Content-Length", compressed.len().to_string());
+          builder.body(compressed.as_slice());
+        }
+        None => {
+          builder.body(body);
+        }
+      }
     }
 
     if let Some(log) = options.log {
@@ -162,4 +237,31 @@ impl PhpResponse {
   pub fn exception(&self) -> Option<String> {
     self.response.exception().map(|v| v.to_owned())
   }
+
+  /// Splits the response body into `chunk_size`-byte pieces (default 64
+  /// KiB), so a large response can be handed to JavaScript as a series of
+  /// `Buffer`s — e.g. via `Readable.from(response.chunks())` — instead of
+  /// one single, fully-materialized `Buffer`.
+  ///
+  /// Note this still requires the whole response body to have been
+  /// produced by PHP and buffered in memory first; only the hand-off to
+  /// JavaScript is chunked.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const response = php.handleRequestSync(request);
+  /// const body = Readable.from(response.chunks());
+  /// ```
+  #[napi]
+  pub fn chunks(&self, chunk_size: Option<u32>) -> Vec<Buffer> {
+    let chunk_size = chunk_size.unwrap_or(64 * 1024).max(1) as usize;
+
+    self
+      .response
+      .body()
+      .chunks(chunk_size)
+      .map(|chunk| chunk.to_vec().into())
+      .collect()
+  }
 }
@@ -3,11 +3,13 @@ use std::{path::Path, str::FromStr};
 // use napi::bindgen_prelude::*;
 use napi::{Error, Result};
 
+use regex::Regex;
+
 use php::{
   rewrite::{
     Condition, ConditionExt, ExistenceCondition, HeaderCondition, HeaderRewriter, HrefRewriter,
-    MethodCondition, MethodRewriter, NonExistenceCondition, PathCondition, PathRewriter, Rewriter,
-    RewriterExt,
+    MethodCondition, MethodRewriter, NonExistenceCondition, PathCondition, PathRewriter,
+    Rewriter, RewriterExt,
   },
   Request, RequestBuilderException,
 };
@@ -32,6 +34,7 @@ pub enum PhpRewriteCond {
   Method(String),
   NotExists,
   Path(String),
+  Query(String, String),
 }
 
 impl Condition for PhpRewriteCond {
@@ -50,6 +53,16 @@ impl Condition for PhpRewriteCond {
       PhpRewriteCond::Path(pattern) => PathCondition::new(pattern.as_str())
         .map(|v| v.matches(request, docroot))
         .unwrap_or_default(),
+      PhpRewriteCond::Query(name, pattern) => Regex::new(pattern)
+        .map(|regex| {
+          request
+            .url()
+            .query_pairs()
+            .find(|(key, _)| key == name.as_str())
+            .map(|(_, value)| regex.is_match(&value))
+            .unwrap_or(false)
+        })
+        .unwrap_or_default(),
     }
   }
 }
@@ -92,6 +105,14 @@ impl TryFrom<&PhpRewriteCondOptions> for Box<PhpRewriteCond> {
         1 => Ok(Box::new(PhpRewriteCond::Path(args[0].to_owned()))),
         _ => Err(Error::from_reason("Wrong number of parameters")),
       },
+      "query" => match args.len() {
+        2 => {
+          let name = args[0].to_owned();
+          let pattern = args[1].to_owned();
+          Ok(Box::new(PhpRewriteCond::Query(name, pattern)))
+        }
+        _ => Err(Error::from_reason("Wrong number of parameters")),
+      },
       _ => Err(Error::from_reason(format!(
         "Unknown condition type: {}",
         cond_type
@@ -117,6 +138,7 @@ pub enum PhpRewriterType {
   Href(String, String),
   Method(String, String),
   Path(String, String),
+  Query(String, String, String),
 }
 
 impl Rewriter for PhpRewriterType {
@@ -146,6 +168,46 @@ impl Rewriter for PhpRewriterType {
           .map(|v| v.rewrite(request.clone(), docroot))
           .unwrap_or(Ok(request))
       }
+      PhpRewriterType::Query(name, pattern, replacement) => {
+        let Ok(regex) = Regex::new(pattern) else {
+          return Ok(request);
+        };
+
+        let pairs: Vec<(String, String)> = request
+          .url()
+          .query_pairs()
+          .map(|(key, value)| (key.into_owned(), value.into_owned()))
+          .collect();
+
+        let mut matched = false;
+        let output: Vec<(String, String)> = pairs
+          .into_iter()
+          .map(|(key, value)| {
+            if key == *name {
+              matched = true;
+              (key, regex.replace(&value, replacement.as_str()).into_owned())
+            } else {
+              (key, value)
+            }
+          })
+          .collect();
+
+        if !matched {
+          return Ok(request);
+        }
+
+        let mut url = request.url().clone();
+        url
+          .query_pairs_mut()
+          .clear()
+          .extend_pairs(output.iter().map(|(key, value)| (key.as_str(), value.as_str())));
+
+        request
+          .extend()
+          .url(url)
+          .expect("re-serialized url should re-parse")
+          .build()
+      }
     }
   }
 }
@@ -197,6 +259,15 @@ impl TryFrom<&PhpRewriterOptions> for Box<PhpRewriterType> {
         }
         _ => Err(Error::from_reason("Wrong number of parameters")),
       },
+      "query" => match args.len() {
+        3 => {
+          let name = args[0].to_owned();
+          let pattern = args[1].to_owned();
+          let replacement = args[2].to_owned();
+          Ok(Box::new(PhpRewriterType::Query(name, pattern, replacement)))
+        }
+        _ => Err(Error::from_reason("Wrong number of parameters")),
+      },
       _ => Err(Error::from_reason(format!(
         "Unknown rewriter type: {}",
         rewriter_type
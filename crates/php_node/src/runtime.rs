@@ -1,4 +1,4 @@
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 use napi::bindgen_prelude::*;
 use napi::{Env, Error, Result, Task};
@@ -7,6 +7,10 @@ use php::{Embed, EmbedRequestError, Handler, Request, Response, RequestRewriter}
 use http_handler::napi::{Request as PhpRequest, Response as PhpResponse};
 use http_rewriter::napi::Rewriter as NapiRewriter;
 
+use crate::listener::{spawn_listener, PhpListenOptions, PhpListenerHandle};
+use crate::stream::PhpRequestBodyStream;
+use crate::upstream::fetch_fallback;
+
 /// Options for creating a new PHP instance.
 #[napi(object)]
 #[derive(Default)]
@@ -19,6 +23,20 @@ pub struct PhpOptions {
   pub throw_request_errors: Option<bool>,
   /// Request rewriter
   pub rewriter: Option<Reference<NapiRewriter>>,
+  /// The maximum time, in milliseconds, a single request may take to
+  /// execute before it is aborted. When unset, requests may run
+  /// indefinitely.
+  pub request_timeout_ms: Option<u32>,
+  /// Serve non-`.php` files under the docroot directly, without going
+  /// through PHP. Defaults to `false`.
+  pub serve_static: Option<bool>,
+  /// Base URL of an upstream HTTP server to forward a request to when no
+  /// matching PHP script is found, instead of returning a static 404. Lets
+  /// php-node front a secondary handler (an SPA, a Node API, etc.) and
+  /// serve PHP only where a script actually exists. Only requests handled
+  /// through `handleRequest`/`handleRequestSync` are forwarded;
+  /// `handleRequestStreamed` and the `listen` listener do not forward.
+  pub fallback_upstream: Option<String>,
 }
 
 /// A PHP instance.
@@ -41,7 +59,9 @@ pub struct PhpOptions {
 #[napi(js_name = "Php")]
 pub struct PhpRuntime {
   embed: Arc<Embed>,
+  runtime: Arc<tokio::runtime::Runtime>,
   throw_request_errors: bool,
+  fallback_upstream: Option<String>,
 }
 
 #[napi]
@@ -63,6 +83,9 @@ impl PhpRuntime {
       argv,
       throw_request_errors,
       rewriter,
+      request_timeout_ms,
+      serve_static,
+      fallback_upstream,
     } = options.unwrap_or_default();
 
     let docroot = docroot
@@ -81,15 +104,30 @@ impl PhpRuntime {
       None
     };
 
-    let embed = match argv {
+    let mut embed = match argv {
       Some(argv) => Embed::new_with_argv(docroot, rewriter, argv),
       None => Embed::new(docroot, rewriter),
     }
-    .map_err(|err| Error::from_reason(err.to_string()))?;
+    .map_err(|err| Error::from_reason(err.to_string()))?
+    .with_serve_static(serve_static.unwrap_or_default());
+
+    // `Embed::with_timeout` arms PHP's own `zend_set_timeout`, which can
+    // actually interrupt a hung script - unlike wrapping the (non-yielding)
+    // `embed.handle()` future in a `tokio::time::timeout`, which only stops
+    // waiting on it without stopping the script itself.
+    if let Some(timeout_ms) = request_timeout_ms {
+      embed = embed.with_timeout(Duration::from_millis(timeout_ms as u64));
+    }
+
+    // Shared across every request instead of spinning up a fresh runtime
+    // (with its own thread pool and timers) per call.
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| Error::from_reason(e.to_string()))?;
 
     Ok(Self {
       embed: Arc::new(embed),
+      runtime: Arc::new(runtime),
       throw_request_errors: throw_request_errors.unwrap_or_default(),
+      fallback_upstream,
     })
   }
 
@@ -121,7 +159,9 @@ impl PhpRuntime {
       PhpRequestTask {
         throw_request_errors: self.throw_request_errors,
         embed: self.embed.clone(),
+        runtime: self.runtime.clone(),
         request: request.deref().clone(),
+        fallback_upstream: self.fallback_upstream.clone(),
       },
       signal,
     )
@@ -150,18 +190,84 @@ impl PhpRuntime {
     let mut task = PhpRequestTask {
       throw_request_errors: self.throw_request_errors,
       embed: self.embed.clone(),
+      runtime: self.runtime.clone(),
       request: request.deref().clone(),
+      fallback_upstream: self.fallback_upstream.clone(),
     };
 
     task.compute().map(Into::<PhpResponse>::into)
   }
+
+  /// Handle a PHP request whose body is streamed in incrementally via a
+  /// [`PhpRequestBodyStream`], instead of being fully materialized up
+  /// front. Useful for large uploads, where `request`'s own `body` should
+  /// be left empty.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const stream = new RequestBodyStream();
+  /// readable.on('data', (chunk) => stream.write(chunk));
+  /// readable.on('end', () => stream.end());
+  ///
+  /// const response = await php.handleRequestStreamed(
+  ///   new Request({ method: 'POST', url: 'http://example.com/upload.php' }),
+  ///   stream
+  /// );
+  /// ```
+  #[napi]
+  pub fn handle_request_streamed(
+    &self,
+    request: &PhpRequest,
+    body: &PhpRequestBodyStream,
+    signal: Option<AbortSignal>,
+  ) -> Result<AsyncTask<PhpStreamedRequestTask>> {
+    Ok(AsyncTask::with_optional_signal(
+      PhpStreamedRequestTask {
+        throw_request_errors: self.throw_request_errors,
+        embed: self.embed.clone(),
+        runtime: self.runtime.clone(),
+        request: request.deref().clone(),
+        body: Some(body.take_body()?),
+      },
+      signal,
+    ))
+  }
+
+  /// Start a built-in HTTP listener that serves requests directly over a
+  /// TCP socket, without going through `handleRequest`/`handleRequestSync`.
+  ///
+  /// This avoids marshalling every request across the N-API boundary: the
+  /// listener accepts connections and dispatches them straight through the
+  /// shared [`Embed`] using a Rust HTTP server.
+  ///
+  /// # Examples
+  ///
+  /// ```js
+  /// const php = new Php({
+  ///   docroot: process.cwd(),
+  ///   argv: process.argv
+  /// });
+  ///
+  /// const listener = php.listen({ port: 3000 });
+  /// console.log(`Listening on ${listener.address}`);
+  ///
+  /// // later
+  /// listener.stop();
+  /// ```
+  #[napi]
+  pub fn listen(&self, options: Option<PhpListenOptions>) -> Result<PhpListenerHandle> {
+    spawn_listener(self.runtime.clone(), self.embed.clone(), options.unwrap_or_default())
+  }
 }
 
 // Task container to run a PHP request in a worker thread.
 pub struct PhpRequestTask {
   embed: Arc<Embed>,
+  runtime: Arc<tokio::runtime::Runtime>,
   request: Request,
   throw_request_errors: bool,
+  fallback_upstream: Option<String>,
 }
 
 #[napi]
@@ -169,10 +275,29 @@ impl Task for PhpRequestTask {
   type Output = Response;
   type JsValue = PhpResponse;
 
-  // Handle the PHP request in the worker thread.
+  // Handle the PHP request in the worker thread, driving it on the runtime
+  // shared across every request rather than spinning up a fresh one here.
   fn compute(&mut self) -> Result<Self::Output> {
-    let runtime = tokio::runtime::Runtime::new().map_err(|e| Error::from_reason(e.to_string()))?;
-    let mut result = runtime.block_on(self.embed.handle(self.request.clone()));
+    // The timeout itself is enforced by `Embed` (`with_timeout` /
+    // `zend_set_timeout`), which can actually interrupt a hung script.
+    // Wrapping this future in a `tokio::time::timeout` would be a no-op:
+    // `embed.handle()` never yields until the PHP script finishes, so the
+    // timer would never get a chance to race it.
+    let mut result = self.runtime.block_on(self.embed.handle(self.request.clone()));
+
+    // A missing script is forwarded to the configured fallback upstream
+    // (if any) rather than immediately turning into a 404, letting
+    // php-node front a secondary handler for routes it doesn't serve.
+    if let (Err(EmbedRequestError::ScriptNotFound(_)), Some(upstream)) =
+      (&result, &self.fallback_upstream)
+    {
+      if let Some(response) = self
+        .runtime
+        .block_on(fetch_fallback(upstream, &self.request))
+      {
+        result = Ok(response);
+      }
+    }
 
     // Translate the various error types into HTTP error responses
     if !self.throw_request_errors {
@@ -184,6 +309,12 @@ impl Task for PhpRequestTask {
               .body(bytes::BytesMut::from("Not Found"))
               .unwrap()
           }
+          EmbedRequestError::Timeout => {
+            http_handler::response::Builder::new()
+              .status(504)
+              .body(bytes::BytesMut::from("Gateway Timeout"))
+              .unwrap()
+          }
           _ => {
             http_handler::response::Builder::new()
               .status(500)
@@ -203,6 +334,57 @@ impl Task for PhpRequestTask {
   }
 }
 
+// Task container to run a streamed PHP request in a worker thread, feeding
+// `php://input` from `body` rather than the request's own materialized body.
+pub struct PhpStreamedRequestTask {
+  embed: Arc<Embed>,
+  runtime: Arc<tokio::runtime::Runtime>,
+  request: Request,
+  throw_request_errors: bool,
+  body: Option<Box<dyn php::RequestBody>>,
+}
+
+#[napi]
+impl Task for PhpStreamedRequestTask {
+  type Output = Response;
+  type JsValue = PhpResponse;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let body = self
+      .body
+      .take()
+      .expect("PhpStreamedRequestTask::compute should only run once");
+
+    let handling = self.embed.handle_streamed(self.request.clone(), body);
+    let mut result = self.runtime.block_on(handling);
+
+    if !self.throw_request_errors {
+      result = result.or_else(|err| {
+        Ok(match err {
+          EmbedRequestError::ScriptNotFound(_script_name) => {
+            http_handler::response::Builder::new()
+              .status(404)
+              .body(bytes::BytesMut::from("Not Found"))
+              .unwrap()
+          }
+          _ => {
+            http_handler::response::Builder::new()
+              .status(500)
+              .body(bytes::BytesMut::from("Internal Server Error"))
+              .unwrap()
+          }
+        })
+      })
+    }
+
+    result.map_err(|err| Error::from_reason(err.to_string()))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(Into::<PhpResponse>::into(output))
+  }
+}
+
 // Wrapper to adapt NapiRewriter to RequestRewriter
 struct NapiRewriterWrapper(NapiRewriter);
 
@@ -0,0 +1,73 @@
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use napi::bindgen_prelude::Buffer;
+use napi::Result;
+
+use php::{RequestBody, StreamedBody};
+
+/// A feed for a request body being streamed into PHP one chunk at a time,
+/// backed by [`StreamedBody`]. Pass the chunks from a Node `Readable` (or
+/// any other incremental source) to `write`, then call `end` once the
+/// upload is fully received.
+///
+/// # Examples
+///
+/// ```js
+/// const stream = new RequestBodyStream();
+/// readable.on('data', (chunk) => stream.write(chunk));
+/// readable.on('end', () => stream.end());
+///
+/// const response = await php.handleRequestStreamed(request, stream);
+/// ```
+#[napi(js_name = "RequestBodyStream")]
+pub struct PhpRequestBodyStream {
+  sender: Mutex<Option<std::sync::mpsc::Sender<Bytes>>>,
+  receiver: Mutex<Option<std::sync::mpsc::Receiver<Bytes>>>,
+}
+
+#[napi]
+impl PhpRequestBodyStream {
+  /// Create a new, empty request body stream.
+  #[napi(constructor)]
+  pub fn constructor() -> Self {
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    PhpRequestBodyStream {
+      sender: Mutex::new(Some(sender)),
+      receiver: Mutex::new(Some(receiver)),
+    }
+  }
+
+  /// Feed the next chunk of the request body.
+  #[napi]
+  pub fn write(&self, chunk: Buffer) -> Result<()> {
+    let sender = self.sender.lock().unwrap();
+    match sender.as_ref() {
+      Some(sender) => sender
+        .send(Bytes::from(chunk.to_vec()))
+        .map_err(|err| napi::Error::from_reason(err.to_string())),
+      None => Err(napi::Error::from_reason("Request body stream already ended")),
+    }
+  }
+
+  /// Signal that no more chunks are coming.
+  #[napi]
+  pub fn end(&self) {
+    self.sender.lock().unwrap().take();
+  }
+}
+
+impl PhpRequestBodyStream {
+  // Takes ownership of the receiver half so it can be handed to a
+  // `StreamedBody`. Can only succeed once per stream.
+  pub(crate) fn take_body(&self) -> Result<Box<dyn RequestBody>> {
+    self
+      .receiver
+      .lock()
+      .unwrap()
+      .take()
+      .map(|receiver| Box::new(StreamedBody::new(receiver)) as Box<dyn RequestBody>)
+      .ok_or_else(|| napi::Error::from_reason("Request body stream already consumed"))
+  }
+}
@@ -0,0 +1,52 @@
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+
+use php::{Request, Response};
+
+// Forwards a request that couldn't be resolved to a PHP script on to a
+// fallback upstream, joining the request's own path and query onto the
+// upstream base URL, and relays the upstream response back unchanged.
+//
+// Returns `None` (rather than a 502) on any failure to reach the upstream,
+// so callers can fall back to their usual error handling.
+pub(crate) async fn fetch_fallback(upstream: &str, request: &Request) -> Option<Response> {
+  let path_and_query = request
+    .uri()
+    .path_and_query()
+    .map(|pq| pq.as_str())
+    .unwrap_or("/");
+
+  let uri: hyper::Uri = format!("{}{}", upstream.trim_end_matches('/'), path_and_query)
+    .parse()
+    .ok()?;
+
+  let mut builder = hyper::Request::builder()
+    .method(request.method().as_str())
+    .uri(uri);
+
+  for (name, value) in request.headers() {
+    builder = builder.header(name, value);
+  }
+
+  let outgoing = builder
+    .body(Full::new(Bytes::copy_from_slice(request.body())))
+    .ok()?;
+
+  let client: Client<HttpConnector, Full<Bytes>> = Client::builder(TokioExecutor::new()).build_http();
+
+  let response = client.request(outgoing).await.ok()?;
+  let status = response.status();
+  let headers = response.headers().clone();
+  let body = response.into_body().collect().await.ok()?.to_bytes();
+
+  let mut builder = http_handler::response::Response::builder().status(status.as_u16());
+
+  for (name, value) in &headers {
+    builder = builder.header(name, value);
+  }
+
+  builder.body(bytes::BytesMut::from(body.as_ref())).ok()
+}